@@ -0,0 +1,191 @@
+use std::{
+    borrow::Cow,
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+use serde::{de, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::InvalidValue;
+
+/// How an [`Altitude`] is measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AltitudeKind {
+    /// Feet above mean sea level.
+    Msl,
+    /// Hundreds of feet on the standard altimeter setting.
+    FlightLevel,
+    /// Feet above ground level, `GND` is 0.
+    Agl,
+}
+
+/// A steerpoint altitude as BMS prints it: `20000`, `FL250`, `500 AGL` or `GND`.
+///
+/// Altitudes compare by their value, `FL20` and `FL020` are the same altitude.
+#[derive(Debug, Clone)]
+pub struct Altitude {
+    pub kind: AltitudeKind,
+    /// Feet, or hundreds of feet for flight levels.
+    pub value: u32,
+    /// As printed in the briefing, `None` for altitudes that weren't parsed.
+    text: Option<Box<str>>,
+}
+
+impl Altitude {
+    pub fn new(kind: AltitudeKind, value: u32) -> Self {
+        Self {
+            kind,
+            value,
+            text: None,
+        }
+    }
+
+    pub fn msl(feet: u32) -> Self {
+        Self::new(AltitudeKind::Msl, feet)
+    }
+
+    pub fn flight_level(level: u32) -> Self {
+        Self::new(AltitudeKind::FlightLevel, level)
+    }
+
+    pub fn agl(feet: u32) -> Self {
+        Self::new(AltitudeKind::Agl, feet)
+    }
+
+    pub fn ground() -> Self {
+        Self::agl(0)
+    }
+
+    /// The altitude in feet, flight levels are taken as-is on the standard setting.
+    pub fn feet(&self) -> u32 {
+        match self.kind {
+            AltitudeKind::Msl | AltitudeKind::Agl => self.value,
+            AltitudeKind::FlightLevel => self.value.saturating_mul(100),
+        }
+    }
+
+    pub fn is_ground(&self) -> bool {
+        self.kind == AltitudeKind::Agl && self.value == 0
+    }
+
+    /// Feet gained (or lost, when negative) flying from `self` to `to`.
+    pub fn climb_to(&self, to: &Altitude) -> i64 {
+        i64::from(to.feet()) - i64::from(self.feet())
+    }
+
+    /// Keeps `text` as the way the briefing printed the altitude.
+    pub(crate) fn with_text(mut self, text: &str) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// The altitude as it was printed in the briefing, `None` when it wasn't parsed.
+    pub fn source(&self) -> Option<&str> {
+        self.text.as_deref()
+    }
+
+    /// Like [`Altitude::source`], written out the way BMS prints it when it wasn't parsed.
+    pub fn text(&self) -> Cow<'_, str> {
+        match &self.text {
+            Some(text) => Cow::Borrowed(text),
+            None => Cow::Owned(self.to_string()),
+        }
+    }
+
+    fn kind_name(&self) -> &'static str {
+        match self.kind {
+            AltitudeKind::Msl => "msl",
+            AltitudeKind::FlightLevel => "flight_level",
+            AltitudeKind::Agl => "agl",
+        }
+    }
+}
+
+impl PartialEq for Altitude {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind && self.value == other.value
+    }
+}
+
+impl Eq for Altitude {}
+
+impl Hash for Altitude {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.kind.hash(state);
+        self.value.hash(state);
+    }
+}
+
+impl FromStr for Altitude {
+    type Err = InvalidValue;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidValue::new("altitude", s);
+        let feet = |n: &str| n.trim().parse::<u32>().map_err(|_| invalid());
+
+        let text = s.trim();
+        let altitude = if text.eq_ignore_ascii_case("GND") {
+            Altitude::ground()
+        } else if let Some(level) = text.strip_prefix("FL") {
+            // Flight levels have three digits, `FL999` is the highest.
+            match feet(level)? {
+                level @ 0..=999 => Altitude::flight_level(level),
+                _ => return Err(invalid()),
+            }
+        } else if let Some(agl) = text.strip_suffix("AGL") {
+            Altitude::agl(feet(agl)?)
+        } else {
+            Altitude::msl(feet(text.strip_suffix("ft").unwrap_or(text))?)
+        };
+
+        Ok(altitude.with_text(text))
+    }
+}
+
+impl fmt::Display for Altitude {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            _ if self.is_ground() => write!(f, "GND"),
+            AltitudeKind::Msl => write!(f, "{}", self.value),
+            AltitudeKind::FlightLevel => write!(f, "FL{:03}", self.value),
+            AltitudeKind::Agl => write!(f, "{} AGL", self.value),
+        }
+    }
+}
+
+impl Serialize for Altitude {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("Altitude", 3)?;
+        s.serialize_field("text", &self.text())?;
+        s.serialize_field("kind", self.kind_name())?;
+        s.serialize_field("feet", &self.feet())?;
+        s.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Altitude {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Repr {
+            text: String,
+            kind: String,
+            feet: u32,
+        }
+
+        let Repr { text, kind, feet } = Repr::deserialize(deserializer)?;
+        let altitude = match kind.as_str() {
+            "msl" => Altitude::msl(feet),
+            "flight_level" => Altitude::flight_level(feet / 100),
+            "agl" => Altitude::agl(feet),
+            kind => {
+                return Err(de::Error::unknown_variant(
+                    kind,
+                    &["msl", "flight_level", "agl"],
+                ))
+            }
+        };
+
+        Ok(altitude.with_text(&text))
+    }
+}
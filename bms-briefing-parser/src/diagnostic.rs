@@ -0,0 +1,121 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::SectionKind;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Something was missing or unreadable and a default was used instead.
+    Warning,
+    /// A row could not be parsed and was dropped or left incomplete.
+    Error,
+}
+
+/// A problem found while parsing, pointing at the section and line it came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub section: SectionKind,
+    /// 1-based line number in the briefing, `None` when it concerns the whole section.
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}, line {}: {}", self.section, line, self.message),
+            None => write!(f, "{}: {}", self.section, self.message),
+        }
+    }
+}
+
+/// Returned by [`crate::Briefing::parse`] when one or more rows could not be parsed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParseError {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(first) = self.diagnostics.first() else {
+            return write!(f, "failed to parse briefing");
+        };
+
+        write!(f, "{}", first)?;
+        if self.diagnostics.len() > 1 {
+            write!(f, " (and {} more)", self.diagnostics.len() - 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Returned when a single value, such as a time or an altitude, can't be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidValue {
+    pub kind: &'static str,
+    pub text: String,
+}
+
+impl InvalidValue {
+    pub(crate) fn new(kind: &'static str, text: &str) -> Self {
+        Self {
+            kind,
+            text: text.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for InvalidValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid {} `{}`", self.kind, self.text)
+    }
+}
+
+impl std::error::Error for InvalidValue {}
+
+#[derive(Debug, Default)]
+pub(crate) struct Diagnostics(Vec<Diagnostic>);
+
+impl Diagnostics {
+    pub fn warning(
+        &mut self,
+        section: SectionKind,
+        line: Option<usize>,
+        message: impl Into<String>,
+    ) {
+        self.push(Severity::Warning, section, line, message.into());
+    }
+
+    pub fn error(&mut self, section: SectionKind, line: Option<usize>, message: impl Into<String>) {
+        self.push(Severity::Error, section, line, message.into());
+    }
+
+    pub fn missing(&mut self, section: SectionKind) {
+        self.warning(section, None, "section not found");
+    }
+
+    pub fn into_vec(self) -> Vec<Diagnostic> {
+        self.0
+    }
+
+    fn push(
+        &mut self,
+        severity: Severity,
+        section: SectionKind,
+        line: Option<usize>,
+        message: String,
+    ) {
+        self.0.push(Diagnostic {
+            severity,
+            section,
+            line,
+            message,
+        });
+    }
+}
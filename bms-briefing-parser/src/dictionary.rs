@@ -0,0 +1,380 @@
+use std::{borrow::Cow, collections::HashMap, sync::OnceLock};
+
+use crate::{InvalidValue, SectionKind};
+
+const ENGLISH: &str = include_str!("../data/dictionaries/en.tsv");
+const GERMAN: &str = include_str!("../data/dictionaries/de.tsv");
+
+/// The row labels and keywords the parser looks for inside sections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Label {
+    PackageMission,
+    TargetArea,
+    TimeOnTarget,
+    Sunrise,
+    Sunset,
+    /// `4 ship` in the package elements.
+    Ship,
+    /// `TOT 08:15:00` in the package elements.
+    Tot,
+    InitialSettings,
+    TimeEvents,
+    Initial,
+    ModesActive,
+    Codes,
+    M4Validity,
+    /// `A until 12:00:00`
+    Until,
+    Policy,
+    On,
+    Off,
+    CodeChange,
+    Automatic,
+    Manual,
+    PositionEvents,
+    /// `STPT 3: M3 4410`, or `12nm north of steerpoint 5` in the threat analysis.
+    Steerpoint,
+    Altitude,
+    Surface,
+    Calm,
+    /// `VRB@05kts`
+    Variable,
+    Knots,
+    Pressure,
+    Visibility,
+    Clouds,
+    Base,
+    Tops,
+    Clear,
+    Contrails,
+    Uhf,
+    Vhf,
+    Tacan,
+    Track,
+    OnStation,
+    /// Marks a plain name such as `Hawk` as a threat system: `Hawk battery`.
+    Site,
+    Target,
+    Airfield,
+    Route,
+    North,
+    South,
+    East,
+    West,
+    Northeast,
+    Northwest,
+    Southeast,
+    Southwest,
+}
+
+impl Label {
+    pub const ALL: [Label; 51] = [
+        Label::PackageMission,
+        Label::TargetArea,
+        Label::TimeOnTarget,
+        Label::Sunrise,
+        Label::Sunset,
+        Label::Ship,
+        Label::Tot,
+        Label::InitialSettings,
+        Label::TimeEvents,
+        Label::Initial,
+        Label::ModesActive,
+        Label::Codes,
+        Label::M4Validity,
+        Label::Until,
+        Label::Policy,
+        Label::On,
+        Label::Off,
+        Label::CodeChange,
+        Label::Automatic,
+        Label::Manual,
+        Label::PositionEvents,
+        Label::Steerpoint,
+        Label::Altitude,
+        Label::Surface,
+        Label::Calm,
+        Label::Variable,
+        Label::Knots,
+        Label::Pressure,
+        Label::Visibility,
+        Label::Clouds,
+        Label::Base,
+        Label::Tops,
+        Label::Clear,
+        Label::Contrails,
+        Label::Uhf,
+        Label::Vhf,
+        Label::Tacan,
+        Label::Track,
+        Label::OnStation,
+        Label::Site,
+        Label::Target,
+        Label::Airfield,
+        Label::Route,
+        Label::North,
+        Label::South,
+        Label::East,
+        Label::West,
+        Label::Northeast,
+        Label::Northwest,
+        Label::Southeast,
+        Label::Southwest,
+    ];
+
+    /// The name of the label in dictionary tables.
+    pub fn key(self) -> &'static str {
+        match self {
+            Label::PackageMission => "package_mission",
+            Label::TargetArea => "target_area",
+            Label::TimeOnTarget => "time_on_target",
+            Label::Sunrise => "sunrise",
+            Label::Sunset => "sunset",
+            Label::Ship => "ship",
+            Label::Tot => "tot",
+            Label::InitialSettings => "initial_settings",
+            Label::TimeEvents => "time_events",
+            Label::Initial => "initial",
+            Label::ModesActive => "modes_active",
+            Label::Codes => "codes",
+            Label::M4Validity => "m4_validity",
+            Label::Until => "until",
+            Label::Policy => "policy",
+            Label::On => "on",
+            Label::Off => "off",
+            Label::CodeChange => "code_change",
+            Label::Automatic => "automatic",
+            Label::Manual => "manual",
+            Label::PositionEvents => "position_events",
+            Label::Steerpoint => "steerpoint",
+            Label::Altitude => "altitude",
+            Label::Surface => "surface",
+            Label::Calm => "calm",
+            Label::Variable => "variable",
+            Label::Knots => "knots",
+            Label::Pressure => "pressure",
+            Label::Visibility => "visibility",
+            Label::Clouds => "clouds",
+            Label::Base => "base",
+            Label::Tops => "tops",
+            Label::Clear => "clear",
+            Label::Contrails => "contrails",
+            Label::Uhf => "uhf",
+            Label::Vhf => "vhf",
+            Label::Tacan => "tacan",
+            Label::Track => "track",
+            Label::OnStation => "on_station",
+            Label::Site => "site",
+            Label::Target => "target",
+            Label::Airfield => "airfield",
+            Label::Route => "route",
+            Label::North => "north",
+            Label::South => "south",
+            Label::East => "east",
+            Label::West => "west",
+            Label::Northeast => "northeast",
+            Label::Northwest => "northwest",
+            Label::Southeast => "southeast",
+            Label::Southwest => "southwest",
+        }
+    }
+}
+
+/// Section headers and row labels in the language of a BMS install.
+///
+/// English and German are built in, others can be loaded with
+/// [`Dictionary::parse`]. Anything a table leaves out is looked up in English.
+#[derive(Debug, Clone)]
+pub struct Dictionary<'a> {
+    language: Cow<'a, str>,
+    headers: HashMap<SectionKind, Cow<'a, str>>,
+    labels: HashMap<Label, Cow<'a, str>>,
+}
+
+impl<'a> Dictionary<'a> {
+    /// The table in `data/dictionaries/en.tsv`.
+    pub fn english() -> &'static Dictionary<'static> {
+        static DICTIONARY: OnceLock<Dictionary<'static>> = OnceLock::new();
+        DICTIONARY.get_or_init(|| Dictionary::parse(ENGLISH).expect("bundled English dictionary"))
+    }
+
+    /// The table in `data/dictionaries/de.tsv`, a sample that wasn't checked
+    /// against a German BMS install.
+    pub fn german() -> &'static Dictionary<'static> {
+        static DICTIONARY: OnceLock<Dictionary<'static>> = OnceLock::new();
+        DICTIONARY.get_or_init(|| Dictionary::parse(GERMAN).expect("bundled German dictionary"))
+    }
+
+    /// Every built-in dictionary, English first.
+    pub fn bundled() -> [&'static Dictionary<'static>; 2] {
+        [Self::english(), Self::german()]
+    }
+
+    /// The built-in dictionary whose headers the briefing uses most, English
+    /// when none of them match.
+    pub fn detect(briefing: &str) -> &'static Dictionary<'static> {
+        let headers: Vec<&str> = briefing
+            .lines()
+            .filter(|l| !l.starts_with('\t') && !l.trim().is_empty())
+            .collect();
+
+        let mut best = (0, Self::english());
+        for dictionary in Self::bundled() {
+            let matches = headers
+                .iter()
+                .filter(|h| dictionary.headers.values().any(|d| starts_with(h, d)))
+                .count();
+            if matches > best.0 {
+                best = (matches, dictionary);
+            }
+        }
+
+        best.1
+    }
+
+    /// Reads a tab separated table: a `language` line, then `header` lines
+    /// keyed by section and `label` lines keyed by [`Label::key`].
+    pub fn parse(text: &'a str) -> Result<Self, InvalidValue> {
+        let mut language = None;
+        let mut headers = HashMap::new();
+        let mut labels = HashMap::new();
+
+        let lines = text
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'));
+
+        for line in lines {
+            let invalid = || InvalidValue::new("dictionary entry", line);
+            let columns: Vec<&str> = line.split('\t').map(str::trim).collect();
+
+            match columns[..] {
+                ["language", name] => language = Some(name),
+                ["header", key, header] => {
+                    let kind = SectionKind::ALL
+                        .into_iter()
+                        .find(|k| k.key() == key)
+                        .ok_or_else(invalid)?;
+                    headers.insert(kind, header.into());
+                }
+                ["label", key, text] => {
+                    let label = Label::ALL
+                        .into_iter()
+                        .find(|l| l.key() == key)
+                        .ok_or_else(invalid)?;
+                    labels.insert(label, text.into());
+                }
+                _ => return Err(invalid()),
+            }
+        }
+
+        Ok(Self {
+            language: language
+                .ok_or_else(|| InvalidValue::new("dictionary language", ""))?
+                .into(),
+            headers,
+            labels,
+        })
+    }
+
+    pub fn into_owned(self) -> Dictionary<'static> {
+        Dictionary {
+            language: crate::owned(self.language),
+            headers: self
+                .headers
+                .into_iter()
+                .map(|(k, v)| (k, crate::owned(v)))
+                .collect(),
+            labels: self
+                .labels
+                .into_iter()
+                .map(|(k, v)| (k, crate::owned(v)))
+                .collect(),
+        }
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// The header this dictionary prints for `kind`.
+    pub fn header(&self, kind: SectionKind) -> &str {
+        match self.headers.get(&kind) {
+            Some(header) => header,
+            None => Self::english()
+                .headers
+                .get(&kind)
+                .map_or(kind.header(), |h| h),
+        }
+    }
+
+    /// How this dictionary spells `label`, the first of its spellings.
+    pub fn label(&self, label: Label) -> &str {
+        self.entry(label).split('|').next().unwrap_or("")
+    }
+
+    /// Every spelling of `label`, separated by `|` in the table.
+    fn entry(&self, label: Label) -> &str {
+        match self.labels.get(&label) {
+            Some(text) => text,
+            None => Self::english().labels.get(&label).map_or("", |l| l),
+        }
+    }
+
+    /// The section a header line starts, in this language or in English.
+    pub(crate) fn section(&self, header: &str) -> Option<SectionKind> {
+        SectionKind::ALL.into_iter().find(|&kind| {
+            starts_with(header, self.header(kind)) || starts_with(header, kind.header())
+        })
+    }
+
+    /// Whether `text` is the label, ignoring case and a trailing colon.
+    pub(crate) fn is(&self, label: Label, text: &str) -> bool {
+        let text = text.trim().trim_end_matches(':').trim_end();
+        self.spellings(label)
+            .any(|l| !l.is_empty() && text.to_lowercase() == l.to_lowercase())
+    }
+
+    /// The rest of `text` after the label and an optional colon, when it starts with it.
+    pub(crate) fn strip_prefix<'t>(&self, label: Label, text: &'t str) -> Option<&'t str> {
+        self.spellings(label).find_map(|l| {
+            let rest = text.get(l.len()..).filter(|_| starts_with(text, l))?;
+            let word_ends = !rest.starts_with(|c: char| c.is_alphanumeric());
+            word_ends.then(|| rest.trim_start().trim_start_matches(':').trim())
+        })
+    }
+
+    /// `text` without the label at its end, `4 ship` gives `4`.
+    pub(crate) fn strip_suffix<'t>(&self, label: Label, text: &'t str) -> Option<&'t str> {
+        self.spellings(label).find_map(|l| {
+            let split = text.len().checked_sub(l.len())?;
+            let (rest, suffix) = (text.get(..split)?, text.get(split..)?);
+            suffix
+                .eq_ignore_ascii_case(l)
+                .then(|| rest.trim())
+                .filter(|_| !l.is_empty())
+        })
+    }
+
+    /// Splits `A until 12:00:00` around the label.
+    pub(crate) fn split_once<'t>(&self, label: Label, text: &'t str) -> Option<(&'t str, &'t str)> {
+        self.spellings(label)
+            .filter(|l| !l.is_empty())
+            .find_map(|l| text.split_once(&format!(" {} ", l)))
+            .map(|(a, b)| (a.trim(), b.trim()))
+    }
+
+    fn spellings(&self, label: Label) -> impl Iterator<Item = &str> {
+        [self.entry(label), Self::english().entry(label)]
+            .into_iter()
+            .flat_map(|entry| entry.split('|'))
+    }
+}
+
+/// `text` starts with `prefix`, ignoring ASCII case.
+fn starts_with(text: &str, prefix: &str) -> bool {
+    !prefix.is_empty()
+        && text
+            .get(..prefix.len())
+            .is_some_and(|t| t.eq_ignore_ascii_case(prefix))
+}
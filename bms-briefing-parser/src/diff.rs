@@ -0,0 +1,225 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Altitude, Briefing, Comm, Frequency, MissionTime, Store, Threat, ThreatLocation};
+
+/// One difference between two briefings, see [`Briefing::diff`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum Change {
+    SteerpointAdded {
+        steerpoint: usize,
+    },
+    SteerpointRemoved {
+        steerpoint: usize,
+    },
+    /// The leg to the steerpoint has a different length or heading.
+    SteerpointMoved {
+        steerpoint: usize,
+        distance: (Option<f64>, Option<f64>),
+        heading: (Option<usize>, Option<usize>),
+    },
+    SteerpointRetimed {
+        steerpoint: usize,
+        from: Option<MissionTime>,
+        to: Option<MissionTime>,
+    },
+    SteerpointAltitudeChanged {
+        steerpoint: usize,
+        from: Option<Altitude>,
+        to: Option<Altitude>,
+    },
+    CommAdded {
+        agency: String,
+        callsign: Option<String>,
+    },
+    CommRemoved {
+        agency: String,
+        callsign: Option<String>,
+    },
+    /// The UHF or VHF frequency of a comm ladder entry.
+    FrequencyChanged {
+        agency: String,
+        callsign: Option<String>,
+        from: Option<Frequency>,
+        to: Option<Frequency>,
+    },
+    /// The stores of one aircraft, empty when the aircraft isn't in the briefing.
+    LoadoutChanged {
+        flight: String,
+        aircraft: String,
+        from: Vec<Store<'static>>,
+        to: Vec<Store<'static>>,
+    },
+    ThreatAdded {
+        system: String,
+        location: Option<ThreatLocation<'static>>,
+    },
+    ThreatRemoved {
+        system: String,
+        location: Option<ThreatLocation<'static>>,
+    },
+}
+
+impl Briefing<'_> {
+    /// What changed from `self` to `other`, in the order of the sections.
+    ///
+    /// Steerpoints are matched by number, comms by agency and callsign,
+    /// loadouts by aircraft and threats by system and location.
+    pub fn diff(&self, other: &Briefing) -> Vec<Change> {
+        let mut changes = vec![];
+
+        steerpoints(self, other, &mut changes);
+        comms(&self.comms, &other.comms, &mut changes);
+        loadouts(self, other, &mut changes);
+        threats(
+            &self.threat_analysis.threats,
+            &other.threat_analysis.threats,
+            &mut changes,
+        );
+
+        changes
+    }
+}
+
+fn steerpoints(from: &Briefing, to: &Briefing, changes: &mut Vec<Change>) {
+    for old in &from.steerpoints {
+        let Some(new) = to
+            .steerpoints
+            .iter()
+            .find(|s| s.steerpoint == old.steerpoint)
+        else {
+            changes.push(Change::SteerpointRemoved {
+                steerpoint: old.steerpoint,
+            });
+            continue;
+        };
+
+        let steerpoint = old.steerpoint;
+        if old.distance != new.distance || old.heading != new.heading {
+            changes.push(Change::SteerpointMoved {
+                steerpoint,
+                distance: (old.distance, new.distance),
+                heading: (old.heading, new.heading),
+            });
+        }
+        if old.time != new.time {
+            changes.push(Change::SteerpointRetimed {
+                steerpoint,
+                from: old.time.clone(),
+                to: new.time.clone(),
+            });
+        }
+        if old.altitude != new.altitude {
+            changes.push(Change::SteerpointAltitudeChanged {
+                steerpoint,
+                from: old.altitude.clone(),
+                to: new.altitude.clone(),
+            });
+        }
+    }
+
+    for new in &to.steerpoints {
+        if !from
+            .steerpoints
+            .iter()
+            .any(|s| s.steerpoint == new.steerpoint)
+        {
+            changes.push(Change::SteerpointAdded {
+                steerpoint: new.steerpoint,
+            });
+        }
+    }
+}
+
+fn comms(from: &[Comm], to: &[Comm], changes: &mut Vec<Change>) {
+    let same = |a: &Comm, b: &Comm| a.agency == b.agency && a.callsign == b.callsign;
+    let names = |comm: &Comm| {
+        (
+            comm.agency.to_string(),
+            comm.callsign.as_deref().map(str::to_string),
+        )
+    };
+
+    for old in from {
+        let (agency, callsign) = names(old);
+        let Some(new) = to.iter().find(|c| same(old, c)) else {
+            changes.push(Change::CommRemoved { agency, callsign });
+            continue;
+        };
+
+        for (from, to) in [(old.uhf, new.uhf), (old.vhf, new.vhf)] {
+            if from != to {
+                changes.push(Change::FrequencyChanged {
+                    agency: agency.clone(),
+                    callsign: callsign.clone(),
+                    from,
+                    to,
+                });
+            }
+        }
+    }
+
+    for new in to {
+        if !from.iter().any(|c| same(c, new)) {
+            let (agency, callsign) = names(new);
+            changes.push(Change::CommAdded { agency, callsign });
+        }
+    }
+}
+
+fn loadouts(from: &Briefing, to: &Briefing, changes: &mut Vec<Change>) {
+    // Every aircraft in either briefing, in the order they first appear.
+    let mut aircraft: Vec<(&str, &str)> = vec![];
+    for flight in from.ordnance.flights.iter().chain(&to.ordnance.flights) {
+        for loadout in &flight.aircraft {
+            let key = (&*flight.callsign, &*loadout.callsign);
+            if !aircraft.contains(&key) {
+                aircraft.push(key);
+            }
+        }
+    }
+
+    let stores = |briefing: &Briefing, (flight, aircraft): (&str, &str)| -> Vec<Store<'static>> {
+        briefing
+            .ordnance
+            .flight(flight)
+            .and_then(|f| f.aircraft.iter().find(|a| a.callsign == aircraft))
+            .map(|a| a.stations.iter().cloned().map(Store::into_owned).collect())
+            .unwrap_or_default()
+    };
+
+    for key in aircraft {
+        let (old, new) = (stores(from, key), stores(to, key));
+        if old != new {
+            changes.push(Change::LoadoutChanged {
+                flight: key.0.to_string(),
+                aircraft: key.1.to_string(),
+                from: old,
+                to: new,
+            });
+        }
+    }
+}
+
+fn threats(from: &[Threat], to: &[Threat], changes: &mut Vec<Change>) {
+    let same = |a: &Threat, b: &Threat| a.system == b.system && a.location == b.location;
+    let names = |threat: &Threat| {
+        (
+            threat.system.to_string(),
+            threat.location.clone().map(ThreatLocation::into_owned),
+        )
+    };
+
+    for old in from {
+        if !to.iter().any(|t| same(old, t)) {
+            let (system, location) = names(old);
+            changes.push(Change::ThreatRemoved { system, location });
+        }
+    }
+    for new in to {
+        if !from.iter().any(|t| same(t, new)) {
+            let (system, location) = names(new);
+            changes.push(Change::ThreatAdded { system, location });
+        }
+    }
+}
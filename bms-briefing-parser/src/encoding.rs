@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+
+/// Text encodings BMS and the tools around it write briefings in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Encoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    /// What BMS itself writes.
+    Windows1252,
+}
+
+impl Encoding {
+    /// Picks the encoding from a byte order mark, falling back to UTF-8 when
+    /// the bytes are valid UTF-8 and Windows-1252 otherwise.
+    pub fn detect(bytes: &[u8]) -> Self {
+        if let Some((encoding, _)) = encoding_rs::Encoding::for_bom(bytes) {
+            return Self::from_rs(encoding);
+        }
+
+        // UTF-16 without a BOM, given away by the zero high bytes of ASCII.
+        if bytes.len() >= 2 && bytes.len().is_multiple_of(2) {
+            let zeros = |start: usize| bytes.iter().skip(start).step_by(2).filter(|&&b| b == 0);
+            let half = bytes.len() / 4;
+            if zeros(1).count() > half && zeros(0).count() == 0 {
+                return Encoding::Utf16Le;
+            }
+            if zeros(0).count() > half && zeros(1).count() == 0 {
+                return Encoding::Utf16Be;
+            }
+        }
+
+        match std::str::from_utf8(bytes) {
+            Ok(_) => Encoding::Utf8,
+            Err(_) => Encoding::Windows1252,
+        }
+    }
+
+    fn from_rs(encoding: &'static encoding_rs::Encoding) -> Self {
+        if encoding == encoding_rs::UTF_16LE {
+            Encoding::Utf16Le
+        } else if encoding == encoding_rs::UTF_16BE {
+            Encoding::Utf16Be
+        } else if encoding == encoding_rs::UTF_8 {
+            Encoding::Utf8
+        } else {
+            Encoding::Windows1252
+        }
+    }
+
+    fn to_rs(self) -> &'static encoding_rs::Encoding {
+        match self {
+            Encoding::Utf8 => encoding_rs::UTF_8,
+            Encoding::Utf16Le => encoding_rs::UTF_16LE,
+            Encoding::Utf16Be => encoding_rs::UTF_16BE,
+            Encoding::Windows1252 => encoding_rs::WINDOWS_1252,
+        }
+    }
+}
+
+/// Briefing text decoded from a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decoded {
+    /// The text with `\n` line endings and without a byte order mark.
+    pub text: String,
+    pub encoding: Encoding,
+    /// Whether some bytes weren't valid in `encoding` and were replaced with `�`.
+    pub had_errors: bool,
+}
+
+/// Decodes a briefing, detecting the encoding unless one is given.
+///
+/// Every character is kept, only line endings are changed to `\n`.
+pub fn decode(bytes: &[u8], encoding: Option<Encoding>) -> Decoded {
+    let encoding = encoding.unwrap_or_else(|| Encoding::detect(bytes));
+
+    // A byte order mark for the expected encoding is skipped, any other is kept as text.
+    let (text, had_errors) = encoding.to_rs().decode_with_bom_removal(bytes);
+
+    let text = match text.contains('\r') {
+        true => text.replace("\r\n", "\n").replace('\r', "\n"),
+        false => text.into_owned(),
+    };
+
+    Decoded {
+        text,
+        encoding,
+        had_errors,
+    }
+}
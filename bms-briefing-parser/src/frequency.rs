@@ -0,0 +1,224 @@
+use std::{fmt, ops::RangeInclusive, str::FromStr};
+
+use serde::{de, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::InvalidValue;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Band {
+    Uhf,
+    Vhf,
+}
+
+impl Band {
+    /// Tunable range of the band in kHz.
+    pub fn range(self) -> RangeInclusive<u32> {
+        match self {
+            Band::Uhf => 225_000..=399_975,
+            Band::Vhf => 116_000..=151_975,
+        }
+    }
+
+    /// The band a frequency in kHz falls in.
+    pub fn of(khz: u32) -> Option<Band> {
+        [Band::Uhf, Band::Vhf]
+            .into_iter()
+            .find(|b| b.range().contains(&khz))
+    }
+}
+
+/// A radio frequency as BMS prints it: `283.200 (3)`, `126.200` or `(3)`.
+///
+/// At least one of the frequency and the preset channel is always set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Frequency {
+    band: Band,
+    khz: Option<u32>,
+    preset: Option<u8>,
+}
+
+impl Frequency {
+    pub const PRESETS: RangeInclusive<u8> = 1..=20;
+
+    /// Parses a frequency from the column of the given band.
+    pub fn parse(text: &str, band: Band) -> Result<Self, InvalidValue> {
+        let invalid = || InvalidValue::new("frequency", text);
+
+        let (mhz, preset) = match text.trim().split_once('(') {
+            Some((mhz, preset)) => {
+                let preset = preset
+                    .strip_suffix(')')
+                    .and_then(|p| p.trim().parse::<u8>().ok())
+                    .filter(|p| Self::PRESETS.contains(p))
+                    .ok_or_else(invalid)?;
+                (mhz.trim(), Some(preset))
+            }
+            None => (text.trim(), None),
+        };
+
+        let khz = match mhz {
+            "" => None,
+            mhz => Some(parse_khz(mhz).ok_or_else(invalid)?),
+        };
+
+        if khz.is_none() && preset.is_none() {
+            return Err(invalid());
+        }
+        if khz.is_some_and(|khz| !band.range().contains(&khz)) {
+            return Err(invalid());
+        }
+
+        Ok(Self { band, khz, preset })
+    }
+
+    pub fn band(self) -> Band {
+        self.band
+    }
+
+    pub fn khz(self) -> Option<u32> {
+        self.khz
+    }
+
+    pub fn mhz(self) -> Option<f64> {
+        self.khz.map(|khz| f64::from(khz) / 1000.0)
+    }
+
+    pub fn preset(self) -> Option<u8> {
+        self.preset
+    }
+
+    /// Whether both tune the same radio, by frequency when both have one, by preset otherwise.
+    pub fn same_channel(self, other: Frequency) -> bool {
+        if self.band != other.band {
+            return false;
+        }
+        match (self.khz, other.khz) {
+            (Some(a), Some(b)) => a == b,
+            _ => self.preset.is_some() && self.preset == other.preset,
+        }
+    }
+}
+
+/// `283.2` or `283.200` to `283200`, without going through floats.
+///
+/// `None` for anything that isn't a number or doesn't fit in a `u32`.
+fn parse_khz(mhz: &str) -> Option<u32> {
+    let (whole, fraction) = mhz.split_once('.').unwrap_or((mhz, ""));
+    if fraction.len() > 3 || !fraction.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let whole: u32 = whole.parse().ok()?;
+    let fraction: u32 = format!("{:0<3}", fraction).parse().ok()?;
+
+    whole.checked_mul(1000)?.checked_add(fraction)
+}
+
+impl fmt::Display for Frequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.khz, self.preset) {
+            (Some(khz), Some(preset)) => {
+                write!(f, "{}.{:03} ({})", khz / 1000, khz % 1000, preset)
+            }
+            (Some(khz), None) => write!(f, "{}.{:03}", khz / 1000, khz % 1000),
+            (None, Some(preset)) => write!(f, "({})", preset),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+impl Serialize for Frequency {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("Frequency", 4)?;
+        s.serialize_field("text", &self.to_string())?;
+        s.serialize_field("band", &self.band)?;
+        s.serialize_field("mhz", &self.mhz())?;
+        s.serialize_field("preset", &self.preset)?;
+        s.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Frequency {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Repr {
+            text: String,
+            band: Band,
+        }
+
+        let repr = Repr::deserialize(deserializer)?;
+        Self::parse(&repr.text, repr.band).map_err(de::Error::custom)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum TacanBand {
+    X,
+    Y,
+}
+
+/// A TACAN channel such as `25Y`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Tacan {
+    pub channel: u8,
+    pub band: TacanBand,
+}
+
+impl Tacan {
+    pub const CHANNELS: RangeInclusive<u8> = 1..=126;
+}
+
+impl FromStr for Tacan {
+    type Err = InvalidValue;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidValue::new("TACAN channel", s);
+
+        let s = s.trim();
+        let band = match s.chars().last() {
+            Some('X' | 'x') => TacanBand::X,
+            Some('Y' | 'y') => TacanBand::Y,
+            _ => return Err(invalid()),
+        };
+        let channel = s[..s.len() - 1]
+            .parse()
+            .ok()
+            .filter(|c| Self::CHANNELS.contains(c))
+            .ok_or_else(invalid)?;
+
+        Ok(Self { channel, band })
+    }
+}
+
+impl fmt::Display for Tacan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{:?}", self.channel, self.band)
+    }
+}
+
+impl Serialize for Tacan {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("Tacan", 3)?;
+        s.serialize_field("text", &self.to_string())?;
+        s.serialize_field("channel", &self.channel)?;
+        s.serialize_field("band", &self.band)?;
+        s.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Tacan {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Repr {
+            channel: u8,
+            band: TacanBand,
+        }
+
+        let repr = Repr::deserialize(deserializer)?;
+        Ok(Self {
+            channel: repr.channel,
+            band: repr.band,
+        })
+    }
+}
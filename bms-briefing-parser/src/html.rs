@@ -0,0 +1,223 @@
+/// The two layouts BMS prints briefings in, depending on whether "HTML
+/// Briefings" is enabled in the BMS config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// `briefing.txt`
+    Text,
+    /// `briefing.html`
+    Html,
+}
+
+impl Format {
+    /// HTML when the document starts with a tag, text otherwise.
+    pub fn detect(text: &str) -> Self {
+        match text.trim_start().starts_with('<') {
+            true => Format::Html,
+            false => Format::Text,
+        }
+    }
+}
+
+/// Turns an HTML briefing back into the layout of `briefing.txt`.
+///
+/// Headings become header lines, every other line is indented by a tab and
+/// table cells are separated by tabs. Text in a `<pre>` is taken as is.
+pub fn html_to_text(html: &str) -> String {
+    let mut writer = TextWriter::default();
+
+    let mut rest = html;
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            writer.text(rest);
+            break;
+        };
+        writer.text(&rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+
+        let Some(end) = rest.find('>') else {
+            break;
+        };
+        let tag = Tag::parse(&rest[1..end]);
+        rest = &rest[end + 1..];
+
+        // Nothing inside these is briefing text.
+        if !tag.closing && matches!(tag.name.as_str(), "head" | "script" | "style" | "title") {
+            let close = format!("</{}", tag.name);
+            let end = rest.to_ascii_lowercase().find(&close).unwrap_or(rest.len());
+            rest = &rest[end..];
+            continue;
+        }
+
+        writer.tag(&tag);
+    }
+
+    writer.end_line(false);
+    writer.out
+}
+
+struct Tag {
+    name: String,
+    closing: bool,
+}
+
+impl Tag {
+    /// Reads the inside of `<td class="x">` or `</tr>`, attributes are dropped.
+    fn parse(inner: &str) -> Self {
+        let closing = inner.starts_with('/');
+        let name = inner
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+
+        Self { name, closing }
+    }
+}
+
+#[derive(Default)]
+struct TextWriter {
+    out: String,
+    cells: Vec<String>,
+    cell: String,
+    /// Cells opened on the current line, a row can start with empty ones.
+    opened: usize,
+    heading: bool,
+    pre: bool,
+}
+
+impl TextWriter {
+    fn text(&mut self, text: &str) {
+        let text = decode_entities(text);
+
+        // A <pre> holds the text layout as is.
+        if self.pre {
+            self.out.push_str(&text);
+            return;
+        }
+
+        // Outside of <pre> runs of whitespace are a single space, line breaks
+        // come from the tags.
+        for word in text.split_whitespace() {
+            if !self.cell.is_empty() && !self.cell.ends_with(' ') {
+                self.cell.push(' ');
+            }
+            self.cell.push_str(word);
+        }
+        if text.ends_with(char::is_whitespace) && !self.cell.is_empty() {
+            self.cell.push(' ');
+        }
+    }
+
+    fn tag(&mut self, tag: &Tag) {
+        match (tag.name.as_str(), tag.closing) {
+            ("td" | "th", false) => {
+                if self.opened > 0 {
+                    self.end_cell();
+                }
+                self.opened += 1;
+            }
+            ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", false) => {
+                self.end_line(false);
+                self.heading = true;
+            }
+            ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", true) => {
+                self.end_line(false);
+                self.heading = false;
+            }
+            ("pre", false) => {
+                self.end_line(false);
+                self.pre = true;
+            }
+            ("pre", true) => {
+                if !self.out.is_empty() && !self.out.ends_with('\n') {
+                    self.out.push('\n');
+                }
+                self.pre = false;
+            }
+            _ if self.pre => {}
+            ("br", _) => self.end_line(true),
+            // A table row is a line even when all its cells are empty.
+            ("tr", true) => self.end_line(self.opened > 0),
+            ("tr" | "p" | "div" | "li" | "table" | "hr", _) => self.end_line(false),
+            _ => {}
+        }
+    }
+
+    fn end_cell(&mut self) {
+        let cell = std::mem::take(&mut self.cell);
+        self.cells.push(cell.trim().to_string());
+    }
+
+    /// Finishes the current line. Empty lines are only kept for a `<br>`, the
+    /// other tags just make sure a new line starts.
+    fn end_line(&mut self, keep_empty: bool) {
+        if self.opened > 0 || !self.cell.trim().is_empty() {
+            self.end_cell();
+        }
+
+        let empty = self.cells.iter().all(String::is_empty);
+        if !empty || keep_empty {
+            if !self.heading {
+                self.out.push('\t');
+            }
+            self.out.push_str(&self.cells.join("\t"));
+            self.out.push('\n');
+        }
+
+        self.cells.clear();
+        self.cell.clear();
+        self.opened = 0;
+    }
+}
+
+/// Replaces the character references HTML briefings use.
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let name = &rest[1..end];
+            let c = match name {
+                "amp" => '&',
+                "lt" => '<',
+                "gt" => '>',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => ' ',
+                "deg" => '°',
+                _ => {
+                    let code = match name.strip_prefix("#x").or(name.strip_prefix("#X")) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => name.strip_prefix('#')?.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+
+    out
+}
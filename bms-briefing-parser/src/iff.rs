@@ -0,0 +1,407 @@
+use std::{fmt, str::FromStr};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    diagnostic::Diagnostics, Dictionary, InvalidValue, Label, MissionTime, Section, SectionKind,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum IffMode {
+    M1,
+    M2,
+    M3,
+    M4,
+}
+
+impl IffMode {
+    pub const ALL: [IffMode; 4] = [IffMode::M1, IffMode::M2, IffMode::M3, IffMode::M4];
+}
+
+impl FromStr for IffMode {
+    type Err = InvalidValue;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().trim_start_matches(['M', 'm']) {
+            "1" => Ok(IffMode::M1),
+            "2" => Ok(IffMode::M2),
+            "3" => Ok(IffMode::M3),
+            "4" => Ok(IffMode::M4),
+            _ => Err(InvalidValue::new("IFF mode", s)),
+        }
+    }
+}
+
+impl fmt::Display for IffMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// An octal transponder code as dialled in, `12` for mode 1 or `4402` for modes 2 and 3.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IffCode {
+    value: u16,
+    digits: u8,
+}
+
+impl IffCode {
+    pub fn value(self) -> u16 {
+        self.value
+    }
+}
+
+impl FromStr for IffCode {
+    type Err = InvalidValue;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() || s.len() > 4 || !s.chars().all(|c| ('0'..='7').contains(&c)) {
+            return Err(InvalidValue::new("IFF code", s));
+        }
+
+        Ok(Self {
+            value: u16::from_str_radix(s, 8).map_err(|_| InvalidValue::new("IFF code", s))?,
+            digits: s.len() as u8,
+        })
+    }
+}
+
+impl fmt::Display for IffCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:0width$o}", self.value, width = self.digits as usize)
+    }
+}
+
+impl Serialize for IffCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for IffCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum M4Key {
+    A,
+    B,
+}
+
+impl FromStr for M4Key {
+    type Err = InvalidValue;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "A" => Ok(M4Key::A),
+            "B" => Ok(M4Key::B),
+            _ => Err(InvalidValue::new("mode 4 key", s)),
+        }
+    }
+}
+
+/// The code for each mode, `None` where the briefing doesn't set one.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IffCodes {
+    pub m1: Option<IffCode>,
+    pub m2: Option<IffCode>,
+    pub m3: Option<IffCode>,
+    pub m4: Option<M4Key>,
+}
+
+impl IffCodes {
+    /// Overwrites the codes that `changes` sets, keeping the others.
+    pub fn apply(&mut self, changes: &IffCodes) {
+        self.m1 = changes.m1.or(self.m1);
+        self.m2 = changes.m2.or(self.m2);
+        self.m3 = changes.m3.or(self.m3);
+        self.m4 = changes.m4.or(self.m4);
+    }
+
+    fn set(&mut self, mode: IffMode, code: &str) -> Result<(), InvalidValue> {
+        let Some(code) = crate::to_option(code.trim()) else {
+            return Ok(());
+        };
+
+        match mode {
+            IffMode::M1 => self.m1 = Some(code.parse()?),
+            IffMode::M2 => self.m2 = Some(code.parse()?),
+            IffMode::M3 => self.m3 = Some(code.parse()?),
+            IffMode::M4 => self.m4 = Some(code.parse()?),
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether each mode is switched on, `None` where the briefing doesn't say.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IffPolicy {
+    pub m1: Option<bool>,
+    pub m2: Option<bool>,
+    pub m3: Option<bool>,
+    pub m4: Option<bool>,
+}
+
+impl IffPolicy {
+    pub fn get(&self, mode: IffMode) -> Option<bool> {
+        match mode {
+            IffMode::M1 => self.m1,
+            IffMode::M2 => self.m2,
+            IffMode::M3 => self.m3,
+            IffMode::M4 => self.m4,
+        }
+    }
+
+    fn set(
+        &mut self,
+        mode: IffMode,
+        policy: &str,
+        dictionary: &Dictionary,
+    ) -> Result<(), InvalidValue> {
+        let on = if dictionary.is(Label::On, policy) {
+            true
+        } else if dictionary.is(Label::Off, policy) {
+            false
+        } else {
+            return Err(InvalidValue::new("IFF policy", policy));
+        };
+
+        match mode {
+            IffMode::M1 => self.m1 = Some(on),
+            IffMode::M2 => self.m2 = Some(on),
+            IffMode::M3 => self.m3 = Some(on),
+            IffMode::M4 => self.m4 = Some(on),
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CodeChange {
+    Automatic,
+    Manual,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct M4Validity {
+    pub key: M4Key,
+    pub until: MissionTime,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct IffInitial {
+    pub modes_active: Vec<IffMode>,
+    pub codes: IffCodes,
+    pub m4_validity: Vec<M4Validity>,
+    pub policy: IffPolicy,
+    pub code_change: Option<CodeChange>,
+}
+
+/// New codes to dial in at a mission time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IffTimeEvent {
+    pub time: MissionTime,
+    pub codes: IffCodes,
+}
+
+/// New codes to dial in when reaching a steerpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IffPositionEvent {
+    pub steerpoint: usize,
+    pub codes: IffCodes,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct Iff {
+    pub initial: IffInitial,
+    pub time_events: Vec<IffTimeEvent>,
+    pub position_events: Vec<IffPositionEvent>,
+}
+
+impl Iff {
+    /// The codes in effect at `time`, the initial codes with every earlier time event applied.
+    ///
+    /// Position events are left out, they depend on where the flight is.
+    pub fn codes_at(&self, time: &MissionTime) -> IffCodes {
+        let mut codes = self.initial.codes;
+        for event in self.time_events.iter().take_while(|e| &e.time <= time) {
+            codes.apply(&event.codes);
+        }
+        codes
+    }
+
+    /// The first time event after `time`.
+    pub fn next_change(&self, time: &MissionTime) -> Option<&IffTimeEvent> {
+        self.time_events.iter().find(|e| &e.time > time)
+    }
+
+    pub(crate) fn from_section(
+        iff: &Section<'_>,
+        dictionary: &Dictionary,
+        diagnostics: &mut Diagnostics,
+    ) -> Self {
+        let mut this = Self::default();
+
+        // Time event columns follow the `M1 M2 M3 M4` header row.
+        let mut columns = IffMode::ALL.to_vec();
+        let mut previous_time = None;
+
+        for (line, text) in iff.lines().skip(1) {
+            let cells: Vec<&str> = text
+                .strip_prefix('\t')
+                .unwrap_or(text)
+                .trim_end()
+                .split('\t')
+                .map(str::trim)
+                .collect();
+
+            let mut warn =
+                |e: InvalidValue| diagnostics.warning(SectionKind::Iff, Some(line), e.to_string());
+
+            // Rows are told apart by the label in their first cell.
+            let label = cells
+                .first()
+                .and_then(|cell| ROW_LABELS.into_iter().find(|&l| dictionary.is(l, cell)));
+
+            match (label, &cells[..]) {
+                (_, [] | [""]) | (Some(Label::InitialSettings | Label::TimeEvents), _) => {}
+                (Some(Label::Initial), [_, values @ ..]) => {
+                    for value in values {
+                        if let Some(modes) = dictionary.strip_prefix(Label::ModesActive, value) {
+                            this.initial.modes_active = modes
+                                .split(',')
+                                .filter(|m| !m.trim().is_empty())
+                                .filter_map(|m| m.parse().map_err(&mut warn).ok())
+                                .collect();
+                        } else if let Some((mode, code)) = mode_value(value) {
+                            this.initial.codes.set(mode, code).unwrap_or_else(&mut warn);
+                        } else if !dictionary.is(Label::Codes, value) {
+                            warn(InvalidValue::new("IFF setting", value));
+                        }
+                    }
+                }
+                (Some(Label::M4Validity), [_, values @ ..]) => {
+                    for value in values {
+                        match m4_validity(value, dictionary) {
+                            Ok(validity) => this.initial.m4_validity.push(validity),
+                            Err(e) => warn(e),
+                        }
+                    }
+                }
+                (Some(Label::Policy), [_, values @ ..]) => {
+                    for value in values {
+                        match mode_value(value) {
+                            Some((mode, policy)) => this
+                                .initial
+                                .policy
+                                .set(mode, policy, dictionary)
+                                .unwrap_or_else(&mut warn),
+                            None => warn(InvalidValue::new("IFF policy", value)),
+                        }
+                    }
+                }
+                (Some(Label::CodeChange), [_, setting, ..]) => {
+                    this.initial.code_change = if dictionary.is(Label::Automatic, setting) {
+                        Some(CodeChange::Automatic)
+                    } else if dictionary.is(Label::Manual, setting) {
+                        Some(CodeChange::Manual)
+                    } else {
+                        warn(InvalidValue::new("code change setting", setting));
+                        None
+                    }
+                }
+                (Some(Label::PositionEvents), [_, values @ ..]) => {
+                    for value in values.iter().filter_map(|v| crate::to_option(v)) {
+                        match position_event(value, dictionary) {
+                            Ok(event) => this.position_events.push(event),
+                            Err(e) => warn(e),
+                        }
+                    }
+                }
+                (_, ["", modes @ ..]) => match modes.iter().map(|m| m.parse()).collect() {
+                    Ok(modes) => columns = modes,
+                    Err(e) => warn(e),
+                },
+                (_, [time, codes @ ..]) => {
+                    let time = match time.parse::<MissionTime>() {
+                        Ok(time) => time,
+                        Err(_) => {
+                            warn(InvalidValue::new("IFF row", time));
+                            continue;
+                        }
+                    };
+                    let time = match &previous_time {
+                        Some(previous) => time.after(previous),
+                        None => time,
+                    };
+                    previous_time = Some(time.clone());
+
+                    let mut event = IffTimeEvent {
+                        time,
+                        codes: IffCodes::default(),
+                    };
+                    for (&mode, code) in columns.iter().zip(codes) {
+                        event.codes.set(mode, code).unwrap_or_else(&mut warn);
+                    }
+                    this.time_events.push(event);
+                }
+            }
+        }
+
+        this
+    }
+}
+
+/// Labels that start a row of their own.
+const ROW_LABELS: [Label; 7] = [
+    Label::InitialSettings,
+    Label::TimeEvents,
+    Label::Initial,
+    Label::M4Validity,
+    Label::Policy,
+    Label::CodeChange,
+    Label::PositionEvents,
+];
+
+/// Splits `M3: 4402` into its mode and value.
+fn mode_value(value: &str) -> Option<(IffMode, &str)> {
+    let (mode, value) = value.split_once(':')?;
+    Some((mode.parse().ok()?, value.trim()))
+}
+
+/// Reads `A until 12:00:00`.
+fn m4_validity(value: &str, dictionary: &Dictionary) -> Result<M4Validity, InvalidValue> {
+    let (key, until) = dictionary
+        .split_once(Label::Until, value)
+        .ok_or_else(|| InvalidValue::new("mode 4 validity", value))?;
+
+    Ok(M4Validity {
+        key: key.parse()?,
+        until: until.parse()?,
+    })
+}
+
+/// Reads `STPT 3: M3 4410`.
+fn position_event(value: &str, dictionary: &Dictionary) -> Result<IffPositionEvent, InvalidValue> {
+    let invalid = || InvalidValue::new("position event", value);
+
+    let (steerpoint, change) = value.split_once(':').ok_or_else(invalid)?;
+    let steerpoint = dictionary
+        .strip_prefix(Label::Steerpoint, steerpoint.trim())
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(invalid)?;
+
+    let mut codes = IffCodes::default();
+    let (mode, code) = change.trim().split_once(' ').ok_or_else(invalid)?;
+    codes.set(mode.parse()?, code)?;
+
+    Ok(IffPositionEvent { steerpoint, codes })
+}
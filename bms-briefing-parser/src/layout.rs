@@ -0,0 +1,184 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Section, SectionIndex, SectionKind};
+
+/// The BMS releases whose briefing layout the parser knows, see [`Layout`].
+///
+/// Only 4.35 so far, an older release gets its own profile once there's a
+/// briefing printed by it to check the offsets against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BmsFormatVersion {
+    /// BMS 4.35 and later.
+    Bms435,
+}
+
+impl BmsFormatVersion {
+    /// Oldest first.
+    pub const ALL: [BmsFormatVersion; 1] = [BmsFormatVersion::Bms435];
+
+    /// The newest release `section` is laid out like, `None` when it's none
+    /// of them. Sections of text fit every release.
+    pub fn of_section(section: &Section) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .rev()
+            .find(|&version| Layout::of(version).fits(section))
+    }
+
+    /// The newest release any table or section of rows in the briefing is laid
+    /// out like, `None` when none of them is. A briefing without such sections
+    /// is taken to be the newest.
+    pub fn detect(index: &SectionIndex) -> Option<Self> {
+        let sections: Vec<&Section> = TABLES
+            .iter()
+            .chain(&ROWS)
+            .filter_map(|&kind| index.get(kind))
+            .collect();
+        if sections.is_empty() {
+            return Some(Self::ALL[Self::ALL.len() - 1]);
+        }
+
+        Self::ALL
+            .into_iter()
+            .rev()
+            .find(|&version| sections.iter().any(|s| Layout::of(version).fits(s)))
+    }
+
+    pub fn layout(self) -> &'static Layout {
+        Layout::of(self)
+    }
+}
+
+/// What goes in each column of the steerpoint table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SteerpointColumn {
+    Number,
+    Description,
+    Time,
+    Distance,
+    Heading,
+    Cas,
+    Altitude,
+    Action,
+    Form,
+    Comments,
+}
+
+/// Where one BMS release prints things in the briefing.
+///
+/// Every section starts with its header line. Offsets count lines from the
+/// header, so the header itself is line 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layout {
+    pub version: BmsFormatVersion,
+    /// First line of a section that is just text, such as the situation.
+    pub text_start: usize,
+    /// Line of the column names in a table.
+    pub columns: usize,
+    /// First row of a table.
+    pub table_start: usize,
+    /// First flight in the ordnance section.
+    pub ordnance_start: usize,
+    pub steerpoint_columns: &'static [SteerpointColumn],
+}
+
+/// The sections printed as a table with column names.
+const TABLES: [SectionKind; 5] = [
+    SectionKind::PilotRoster,
+    SectionKind::PackageElements,
+    SectionKind::Steerpoints,
+    SectionKind::CommLadder,
+    SectionKind::Support,
+];
+
+/// The sections printed in rows without column names of their own.
+const ROWS: [SectionKind; 4] = [
+    SectionKind::ThreatAnalysis,
+    SectionKind::Iff,
+    SectionKind::Ordnance,
+    SectionKind::Weather,
+];
+
+const BMS_435: Layout = Layout {
+    version: BmsFormatVersion::Bms435,
+    text_start: 2,
+    columns: 2,
+    table_start: 4,
+    ordnance_start: 3,
+    steerpoint_columns: &[
+        SteerpointColumn::Number,
+        SteerpointColumn::Description,
+        SteerpointColumn::Time,
+        SteerpointColumn::Distance,
+        SteerpointColumn::Heading,
+        SteerpointColumn::Cas,
+        SteerpointColumn::Altitude,
+        SteerpointColumn::Action,
+        SteerpointColumn::Form,
+        SteerpointColumn::Comments,
+    ],
+};
+
+impl Layout {
+    pub fn of(version: BmsFormatVersion) -> &'static Layout {
+        match version {
+            BmsFormatVersion::Bms435 => &BMS_435,
+        }
+    }
+
+    /// The layout of the newest BMS release.
+    pub fn latest() -> &'static Layout {
+        Self::of(BmsFormatVersion::ALL[BmsFormatVersion::ALL.len() - 1])
+    }
+
+    /// Where `column` is in a steerpoint row.
+    pub fn steerpoint_column(&self, column: SteerpointColumn) -> Option<usize> {
+        self.steerpoint_columns.iter().position(|&c| c == column)
+    }
+
+    /// Whether `section` is laid out like this, sections of text always are.
+    fn fits(&self, section: &Section) -> bool {
+        match section.kind {
+            Some(kind) if TABLES.contains(&kind) => self.fits_table(section),
+            Some(kind) if ROWS.contains(&kind) => self.fits_rows(section),
+            _ => true,
+        }
+    }
+
+    fn fits_table(&self, section: &Section) -> bool {
+        let lines: Vec<&str> = section.text.lines().collect();
+        let blank = |i: usize| lines.get(i).is_some_and(|l| l.trim().is_empty());
+
+        // Tables with nothing in them say nothing about the layout.
+        if lines.len() <= self.columns + 1 {
+            return true;
+        }
+
+        let columns = lines[self.columns]
+            .split('\t')
+            .filter(|c| !c.trim().is_empty());
+        let columns_fit = section.kind != Some(SectionKind::Steerpoints)
+            || columns.count() == self.steerpoint_columns.len();
+
+        // Everything between the header and the column names is blank,
+        // and so is everything between the column names and the rows.
+        (1..self.columns).all(blank)
+            && !blank(self.columns)
+            && (self.columns + 1..self.table_start).all(blank)
+            && !blank(self.table_start)
+            && columns_fit
+    }
+
+    /// Everything between the header and the first row is blank.
+    fn fits_rows(&self, section: &Section) -> bool {
+        let lines: Vec<&str> = section.text.lines().collect();
+        let blank = |i: usize| lines.get(i).is_some_and(|l| l.trim().is_empty());
+        let start = match section.kind {
+            Some(SectionKind::Ordnance) => self.ordnance_start,
+            _ => self.text_start,
+        };
+
+        lines.len() <= start || ((1..start).all(blank) && !blank(start))
+    }
+}
@@ -1,45 +1,358 @@
-use std::collections::HashMap;
+mod altitude;
+mod diagnostic;
+mod dictionary;
+mod diff;
+mod encoding;
+mod frequency;
+mod html;
+mod iff;
+mod layout;
+mod ordnance;
+mod section;
+mod support;
+mod threat;
+mod time;
+mod weather;
+mod writer;
+
+use std::{borrow::Cow, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+pub use altitude::{Altitude, AltitudeKind};
+pub use diagnostic::{Diagnostic, InvalidValue, ParseError, Severity};
+pub use dictionary::{Dictionary, Label};
+pub use diff::Change;
+pub use encoding::{decode, Decoded, Encoding};
+pub use frequency::{Band, Frequency, Tacan, TacanBand};
+pub use html::{html_to_text, Format};
+pub use iff::{
+    CodeChange, Iff, IffCode, IffCodes, IffInitial, IffMode, IffPolicy, IffPositionEvent,
+    IffTimeEvent, M4Key, M4Validity,
+};
+pub use layout::{BmsFormatVersion, Layout, SteerpointColumn};
+pub use ordnance::{AircraftLoadout, Ordnance, OrdnanceFlight, Store};
+pub use section::{Section, SectionIndex, SectionKind};
+pub use support::{Support, SupportAsset, SupportRole};
+pub use threat::{
+    LocationReference, Threat, ThreatAnalysis, ThreatDatabase, ThreatKind, ThreatLocation,
+    ThreatSystem,
+};
+pub use time::{MissionTime, TimeWindow};
+pub use weather::{Pressure, Weather, WindLayer};
+
+use diagnostic::Diagnostics;
+
+/// Version of the JSON model, bumped whenever a field is renamed or removed.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// JSON schema of a serialized [`Briefing`], the other types are in its `$defs`.
+pub const SCHEMA: &str = include_str!("../schema/briefing.v1.schema.json");
+
+/// Every section of a BMS `briefing.txt`, parsed in one go.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct Briefing<'a> {
+    pub overview: Overview<'a>,
+    pub sitrep: Sitrep,
+    pub roster: Vec<PilotRoster<'a>>,
+    pub elements: Vec<PackageElement<'a>>,
+    pub threat_analysis: ThreatAnalysis<'a>,
+    pub steerpoints: Vec<Steerpoint<'a>>,
+    pub comms: Vec<Comm<'a>>,
+    pub iff: Iff,
+    pub ordnance: Ordnance<'a>,
+    pub weather: Weather,
+    pub support: Support<'a>,
+    pub roe: RulesOfEngagement,
+    pub emergency: Emergency,
+}
+
+/// A leniently parsed briefing together with everything that went wrong on the way.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct Parsed<'a> {
+    pub briefing: Briefing<'a>,
+    pub diagnostics: Vec<Diagnostic>,
+    /// The newest BMS release a section was laid out like, `None` when none was.
+    #[serde(default)]
+    pub version: Option<BmsFormatVersion>,
+    /// The sections found in the briefing, in the order they're printed.
+    #[serde(skip)]
+    pub sections: Vec<SectionKind>,
+}
+
+impl Parsed<'_> {
+    pub fn into_owned(self) -> Parsed<'static> {
+        Parsed {
+            briefing: self.briefing.into_owned(),
+            diagnostics: self.diagnostics,
+            version: self.version,
+            sections: self.sections,
+        }
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|d| d.severity == Severity::Error)
+    }
+}
+
+impl<'a> Briefing<'a> {
+    /// Copies everything still borrowed from the briefing text, so the result
+    /// can be kept around after the text is gone.
+    pub fn into_owned(self) -> Briefing<'static> {
+        Briefing {
+            overview: self.overview.into_owned(),
+            sitrep: self.sitrep,
+            roster: self
+                .roster
+                .into_iter()
+                .map(PilotRoster::into_owned)
+                .collect(),
+            elements: self
+                .elements
+                .into_iter()
+                .map(PackageElement::into_owned)
+                .collect(),
+            threat_analysis: self.threat_analysis.into_owned(),
+            steerpoints: self
+                .steerpoints
+                .into_iter()
+                .map(Steerpoint::into_owned)
+                .collect(),
+            comms: self.comms.into_iter().map(Comm::into_owned).collect(),
+            iff: self.iff,
+            ordnance: self.ordnance.into_owned(),
+            weather: self.weather,
+            support: self.support.into_owned(),
+            roe: self.roe,
+            emergency: self.emergency,
+        }
+    }
+
+    /// The comm ladder followed by every support asset that isn't on it yet.
+    pub fn comms_with_support(&self) -> Vec<Comm<'a>> {
+        let mut comms = self.comms.clone();
+        for comm in self.support.assets.iter().filter_map(SupportAsset::comm) {
+            let listed = comms.iter().any(|c| {
+                c.callsign == comm.callsign
+                    || c.frequencies()
+                        .any(|f| comm.frequencies().any(|s| s.same_channel(f)))
+            });
+            if !listed {
+                comms.push(comm);
+            }
+        }
+        comms
+    }
+
+    /// Parses the briefing, failing if any row could not be parsed.
+    ///
+    /// Missing sections and unreadable optional values are not errors, use
+    /// [`Briefing::parse_lenient`] to see those as well.
+    pub fn parse(briefing: &'a str) -> Result<Self, ParseError> {
+        let parsed = Self::parse_lenient(briefing);
+        if !parsed.has_errors() {
+            return Ok(parsed.briefing);
+        }
+
+        let diagnostics = parsed
+            .diagnostics
+            .into_iter()
+            .filter(|d| d.severity == Severity::Error)
+            .collect();
+
+        Err(ParseError { diagnostics })
+    }
+
+    /// Decodes and leniently parses a `briefing.txt` or `briefing.html` as read from disk.
+    ///
+    /// The encoding and format are detected, see [`Encoding::detect`] and
+    /// [`Format::detect`]. The result owns its text, so it doesn't borrow from `bytes`.
+    pub fn parse_bytes(bytes: &[u8]) -> Parsed<'static> {
+        Self::parse_bytes_as(bytes, None)
+    }
+
+    /// Like [`Briefing::parse_bytes`], decoding with `encoding` when it's given.
+    pub fn parse_bytes_as(bytes: &[u8], encoding: Option<Encoding>) -> Parsed<'static> {
+        Self::parse_bytes_with(bytes, encoding, None).0
+    }
+
+    /// Like [`Briefing::parse_bytes_as`], reading headers and labels from
+    /// `dictionary` when it's given. Also returns the decoded file.
+    pub fn parse_bytes_with(
+        bytes: &[u8],
+        encoding: Option<Encoding>,
+        dictionary: Option<&Dictionary>,
+    ) -> (Parsed<'static>, Decoded) {
+        let decoded = decode(bytes, encoding);
+        let html;
+        let text = match Format::detect(&decoded.text) {
+            Format::Text => &decoded.text,
+            Format::Html => {
+                html = html_to_text(&decoded.text);
+                &html
+            }
+        };
 
-use serde::Serialize;
+        let dictionary = dictionary.unwrap_or_else(|| Dictionary::detect(text));
+        let parsed = Briefing::parse_lenient_with(text, dictionary).into_owned();
+        (parsed, decoded)
+    }
 
-#[derive(Default, Debug, Clone, Serialize)]
+    /// Leniently parses the HTML briefing BMS prints with "HTML Briefings" enabled.
+    pub fn parse_html(html: &str) -> Parsed<'static> {
+        Briefing::parse_lenient(&html_to_text(html)).into_owned()
+    }
+
+    /// Parses as much of the briefing as possible, skipping malformed rows.
+    ///
+    /// The language is detected from the section headers, see [`Dictionary::detect`].
+    pub fn parse_lenient(briefing: &'a str) -> Parsed<'a> {
+        Self::parse_lenient_with(briefing, Dictionary::detect(briefing))
+    }
+
+    /// Like [`Briefing::parse_lenient`], reading headers and labels from `dictionary`.
+    pub fn parse_lenient_with(briefing: &'a str, dictionary: &Dictionary) -> Parsed<'a> {
+        let index = SectionIndex::with_dictionary(briefing, dictionary);
+        let mut diagnostics = Diagnostics::default();
+        let d = &mut diagnostics;
+
+        // Sections of text are read whatever the layout. Tables and rows are
+        // read with the layout of their own section, and only when it's known.
+        let version = BmsFormatVersion::detect(&index);
+        let layout = version.map_or(Layout::latest(), Layout::of);
+
+        let overview = section(&index, SectionKind::Overview, d, |s, d| {
+            Overview::from_section(s, layout, dictionary, d)
+        });
+        let own = overview.callsign.clone();
+
+        let briefing = Self {
+            sitrep: section(&index, SectionKind::Situation, d, |s, _| {
+                Sitrep::from_section(s, layout)
+            }),
+            roster: table(&index, SectionKind::PilotRoster, d, |s, layout, _| {
+                PilotRoster::from_section(s, layout, &own)
+            }),
+            elements: table(&index, SectionKind::PackageElements, d, |s, layout, d| {
+                PackageElement::from_section(s, layout, &own, dictionary, d)
+            }),
+            threat_analysis: table(&index, SectionKind::ThreatAnalysis, d, |s, layout, d| {
+                ThreatAnalysis::from_section(s, layout, dictionary, d)
+            }),
+            steerpoints: table(&index, SectionKind::Steerpoints, d, |s, layout, d| {
+                Steerpoint::from_section(s, layout, d)
+            }),
+            comms: table(&index, SectionKind::CommLadder, d, |s, layout, d| {
+                Comm::from_section(s, layout, d)
+            }),
+            iff: table(&index, SectionKind::Iff, d, |s, _, d| {
+                Iff::from_section(s, dictionary, d)
+            }),
+            ordnance: table(&index, SectionKind::Ordnance, d, |s, layout, d| {
+                Ordnance::from_section(s, layout, d)
+            }),
+            weather: table(&index, SectionKind::Weather, d, |s, layout, d| {
+                Weather::from_section(s, layout, dictionary, d)
+            }),
+            support: table(&index, SectionKind::Support, d, |s, layout, d| {
+                Support::from_section(s, layout, dictionary, d)
+            }),
+            roe: section(&index, SectionKind::RulesOfEngagement, d, |s, _| {
+                RulesOfEngagement::from_section(s, layout)
+            }),
+            emergency: section(&index, SectionKind::EmergencyProcedures, d, |s, _| {
+                Emergency::from_section(s, layout)
+            }),
+            overview,
+        };
+
+        Parsed {
+            briefing,
+            diagnostics: diagnostics.into_vec(),
+            version,
+            sections: index.sections().iter().filter_map(|s| s.kind).collect(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct Overview<'a> {
-    pub callsign: &'a str,
-    pub mission_type: &'a str,
+    pub callsign: Cow<'a, str>,
+    pub mission_type: Cow<'a, str>,
     pub package_id: i32,
-    pub package_description: &'a str,
-    pub package_mission: &'a str,
-    pub target_area: &'a str,
-    pub time_on_target: &'a str,
-    pub sunrise: &'a str,
-    pub sunset: &'a str,
+    pub package_description: Cow<'a, str>,
+    pub package_mission: Cow<'a, str>,
+    pub target_area: Cow<'a, str>,
+    pub time_on_target: Cow<'a, str>,
+    pub sunrise: Cow<'a, str>,
+    pub sunset: Cow<'a, str>,
 }
 
 impl<'a> Overview<'a> {
-    pub fn from_briefing(briefing: &'a str) -> Self {
-        let Some(overview) = extract_group(briefing, "Mission Overview") else {
+    pub fn into_owned(self) -> Overview<'static> {
+        Overview {
+            callsign: owned(self.callsign),
+            mission_type: owned(self.mission_type),
+            package_id: self.package_id,
+            package_description: owned(self.package_description),
+            package_mission: owned(self.package_mission),
+            target_area: owned(self.target_area),
+            time_on_target: owned(self.time_on_target),
+            sunrise: owned(self.sunrise),
+            sunset: owned(self.sunset),
+        }
+    }
+
+    fn from_section(
+        overview: &Section<'a>,
+        layout: &Layout,
+        dictionary: &Dictionary,
+        diagnostics: &mut Diagnostics,
+    ) -> Self {
+        let lines: Vec<(usize, &str)> = overview
+            .lines()
+            .skip(layout.text_start)
+            .map(|(n, l)| (n, l.trim()))
+            .collect();
+
+        let Some(&(_, first)) = lines.first() else {
+            diagnostics.error(SectionKind::Overview, None, "missing callsign line");
             return Default::default();
         };
 
-        let lines: Vec<&str> = overview.lines().skip(2).map(str::trim).collect();
-
-        let callsign = lines.first().unwrap_or(&"").split(' ').nth(0).unwrap_or("");
-        let mission_type = lines
-            .first()
-            .unwrap_or(&"")
+        let callsign = first.split(' ').nth(0).unwrap_or("");
+        let mission_type = first
             .split(' ')
             .nth(1)
             .map(|s| s.trim_matches(['(', ')']))
             .unwrap_or("");
 
-        let package_id: i32 = lines[2]
+        let Some(&(package_line, package)) = lines.get(2) else {
+            diagnostics.error(SectionKind::Overview, None, "missing package line");
+            return Self {
+                callsign: callsign.into(),
+                mission_type: mission_type.into(),
+                ..Default::default()
+            };
+        };
+
+        let package_id: i32 = package
             .split(':')
             .nth(1) // Take the second part after splitting by ':'
             .and_then(|s| s.split_whitespace().next()) // Take the first word after trimming whitespace
             .and_then(|s| s.parse().ok()) // Parse the first word into an i32
-            .unwrap_or(0); // Unwrap the result
+            .unwrap_or_else(|| {
+                diagnostics.warning(
+                    SectionKind::Overview,
+                    Some(package_line),
+                    format!("no package number in `{}`", package),
+                );
+                0
+            });
 
-        let package_description = lines[2]
+        let package_description = package
             .split(':')
             .nth(1)
             .unwrap_or("")
@@ -49,89 +362,88 @@ impl<'a> Overview<'a> {
             .map(|s| s.trim_matches(|c| c == ')'))
             .unwrap_or("");
 
-        let mut data = HashMap::<&'a str, &'a str>::new();
+        let mut data = vec![];
 
-        for line in lines.iter().skip(2) {
+        for (_, line) in lines.iter().skip(2) {
             let l = line.split_once('\t').unwrap_or_default();
             if !l.0.is_empty() {
-                data.insert(l.0.trim_matches(|c| c == ':'), l.1);
+                data.push(l);
             }
         }
 
-        let package_mission = data.get("Pkg-Mission").unwrap_or(&"");
-        let sunset = data.get("Sunset").unwrap_or(&"");
-        let sunrise = data.get("Sunrise").unwrap_or(&"");
+        let value = |label: Label| -> &'a str {
+            data.iter()
+                .find(|(key, _)| dictionary.is(label, key))
+                .map_or("", |&(_, value)| value)
+        };
+
+        let package_mission = value(Label::PackageMission);
+        let sunset = value(Label::Sunset);
+        let sunrise = value(Label::Sunrise);
 
-        let target_area = data.get("Target Area").unwrap_or(&"");
-        let time_on_target = data.get("Time on Target").unwrap_or(&"");
+        let target_area = value(Label::TargetArea);
+        let time_on_target = value(Label::TimeOnTarget);
 
         Self {
-            callsign,
-            mission_type,
+            callsign: callsign.into(),
+            mission_type: mission_type.into(),
             package_id,
-            package_description,
-            package_mission,
-            target_area,
-            time_on_target,
-            sunrise,
-            sunset,
+            package_description: package_description.into(),
+            package_mission: package_mission.into(),
+            target_area: target_area.into(),
+            time_on_target: time_on_target.into(),
+            sunrise: sunrise.into(),
+            sunset: sunset.into(),
         }
     }
 }
 
-#[derive(Default, Debug, Clone, Serialize)]
-pub struct Sitrep(pub String);
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct Sitrep {
+    pub text: String,
+}
 
 impl Sitrep {
-    pub fn from_briefing(briefing: &str) -> Self {
-        let Some(sitrep) = extract_group(briefing, "Situation") else {
-            return Default::default();
-        };
-
+    fn from_section(sitrep: &Section, layout: &Layout) -> Self {
         let lines: Vec<&str> = sitrep
+            .text
             .lines()
-            .skip(2)
+            .skip(layout.text_start)
             .map(str::trim)
             .filter(|&l| !l.is_empty())
             .collect();
 
         let sitrep = lines.join("\n");
 
-        Self(sitrep)
+        Self { text: sitrep }
     }
 }
 
-#[derive(Default, Debug, Clone, Serialize)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct PilotRoster<'a> {
     pub primary: bool,
-    pub callsign: &'a str,
-    pub lead: &'a str,
-    pub wing: &'a str,
-    pub element: &'a str,
-    pub four: &'a str,
+    pub callsign: Cow<'a, str>,
+    pub lead: Cow<'a, str>,
+    pub wing: Cow<'a, str>,
+    pub element: Cow<'a, str>,
+    pub four: Cow<'a, str>,
 }
 
 impl<'a> PilotRoster<'a> {
-    pub fn from_briefing(briefing: &'a str) -> Vec<Self> {
-        let Some(overview) = extract_group(briefing, "Mission Overview") else {
-            return Default::default();
-        };
-
-        let lines: Vec<&str> = overview.lines().skip(2).collect();
-        let own = lines
-            .first()
-            .unwrap_or(&"")
-            .split(' ')
-            .nth(0)
-            .unwrap_or("")
-            .trim();
-
-        let Some(data) = extract_group(briefing, "Pilot Roster") else {
-            return Default::default();
-        };
+    pub fn into_owned(self) -> PilotRoster<'static> {
+        PilotRoster {
+            primary: self.primary,
+            callsign: owned(self.callsign),
+            lead: owned(self.lead),
+            wing: owned(self.wing),
+            element: owned(self.element),
+            four: owned(self.four),
+        }
+    }
 
+    fn from_section(data: &Section<'a>, layout: &Layout, own: &str) -> Vec<Self> {
         let mut roster = vec![];
-        let lines: Vec<&str> = data.lines().skip(4).collect();
+        let lines: Vec<&str> = data.text.lines().skip(layout.table_start).collect();
         for line in lines {
             let mut parts = line.split('\t').map(str::trim).filter(|l| !l.is_empty());
             let callsign = parts.next().unwrap_or("");
@@ -144,11 +456,11 @@ impl<'a> PilotRoster<'a> {
             let four = parts.next().unwrap_or("N/A");
             let r = Self {
                 primary,
-                callsign,
-                lead,
-                wing,
-                element,
-                four,
+                callsign: callsign.into(),
+                lead: lead.into(),
+                wing: wing.into(),
+                element: element.into(),
+                four: four.into(),
             };
             roster.push(r);
         }
@@ -157,165 +469,275 @@ impl<'a> PilotRoster<'a> {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
-pub struct PackageElement {
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageElement<'a> {
     pub is_primary: bool,
-    pub callsign: String,
-    pub flight: String,
-    pub role: String,
-    pub aircraft: String,
-    pub task: String,
+    pub callsign: Cow<'a, str>,
+    pub flight_number: Option<u32>,
+    /// Number of aircraft, `4 ship` is 4.
+    pub size: Option<u8>,
+    /// What the flight does, `Strike` or `Escort`.
+    pub role: Option<Cow<'a, str>>,
+    /// `Primary`, `Secondary` or `Support`.
+    pub priority: Option<Cow<'a, str>>,
+    pub aircraft: Option<Cow<'a, str>>,
+    pub squadron: Option<Cow<'a, str>>,
+    pub task: Option<Cow<'a, str>>,
+    /// Time on target, for flights that have one.
+    pub tot: Option<MissionTime>,
+    /// When the flight is on task.
+    pub window: Option<TimeWindow>,
+    /// Cells beyond the two lines BMS usually prints.
+    pub notes: Vec<Cow<'a, str>>,
 }
 
-impl PackageElement {
-    pub fn from_briefing(briefing: &str) -> Vec<Self> {
-        let Some(overview) = extract_group(briefing, "Mission Overview") else {
-            return Default::default();
-        };
+impl<'a> PackageElement<'a> {
+    pub fn into_owned(self) -> PackageElement<'static> {
+        PackageElement {
+            is_primary: self.is_primary,
+            callsign: owned(self.callsign),
+            flight_number: self.flight_number,
+            size: self.size,
+            role: self.role.map(owned),
+            priority: self.priority.map(owned),
+            aircraft: self.aircraft.map(owned),
+            squadron: self.squadron.map(owned),
+            task: self.task.map(owned),
+            tot: self.tot,
+            window: self.window,
+            notes: self.notes.into_iter().map(owned).collect(),
+        }
+    }
 
-        let lines: Vec<&str> = overview.lines().skip(2).collect();
-        let own = lines
-            .first()
-            .unwrap_or(&"")
-            .split(' ')
-            .nth(0)
-            .unwrap_or("")
-            .trim();
+    fn from_section(
+        elements: &Section<'a>,
+        layout: &Layout,
+        own: &str,
+        dictionary: &Dictionary,
+        diagnostics: &mut Diagnostics,
+    ) -> Vec<Self> {
+        let mut flights: Vec<Self> = vec![];
+
+        // A flight starts on a line with its callsign, the lines after it leave
+        // the callsign column empty.
+        let lines = elements
+            .lines()
+            .skip(layout.table_start)
+            .map(|(n, l)| (n, l.strip_prefix('\t').unwrap_or(l).trim_end()))
+            .filter(|(_, l)| !l.trim().is_empty());
 
-        let Some(elements) = extract_group(briefing, "Package Elements") else {
-            return Default::default();
-        };
+        for (line, text) in lines {
+            let mut cells = text.split('\t').map(str::trim);
+            let callsign = cells.next().unwrap_or("");
 
-        let lines: Vec<&str> = elements
-            .lines()
-            .skip(4)
-            .map(str::trim)
-            .filter(|l| !l.is_empty())
-            .collect();
+            if !callsign.is_empty() {
+                flights.push(Self::new(callsign, own));
+            }
+            let Some(flight) = flights.last_mut() else {
+                diagnostics.error(
+                    SectionKind::PackageElements,
+                    Some(line),
+                    "line without a flight callsign",
+                );
+                continue;
+            };
 
-        let mut flights = vec![];
-        for f in (0..lines.len() - 1).step_by(2) {
-            let lines: Vec<&str> = vec![lines[f], lines[f + 1]];
-
-            let line1: Vec<&str> = lines.first().unwrap_or(&"").split('\t').collect();
-            let line2: Vec<&str> = lines[1].split('\t').collect();
-
-            let is_primary = line1.first().unwrap_or(&"").contains(own);
-
-            let callsign = line1.first().unwrap_or(&"").to_string();
-            let flight = format!("{}\n{}", line1[1], line2.first().unwrap_or(&""));
-            let role = format!("{}\n{}", line1[2], line2[1]);
-            let aircraft = format!("{}\n{}", line1[3], line2[2]);
-            let task = format!("{}\n{}", line1[4], line2[3]);
-
-            flights.push(PackageElement {
-                is_primary,
-                callsign,
-                flight,
-                role,
-                aircraft,
-                task,
-            })
+            for (column, value) in cells.enumerate() {
+                if let Some(value) = to_option(value).filter(|v| !v.is_empty()) {
+                    flight.fill(column, value, line, dictionary, diagnostics);
+                }
+            }
         }
 
         flights
     }
-}
-
-#[derive(Default, Debug, Clone, Serialize)]
-pub struct ThreatAnalysis<'a>(pub &'a str);
 
-impl<'a> ThreatAnalysis<'a> {
-    pub fn from_briefing(briefing: &'a str) -> Self {
-        let Some(threat) = extract_group(briefing, "Threat Analysis") else {
-            return Default::default();
-        };
-
-        let threat = threat.splitn(3, '\n').last().unwrap_or("");
+    fn new(callsign: &'a str, own: &str) -> Self {
+        Self {
+            is_primary: !own.is_empty() && callsign.contains(own),
+            callsign: callsign.into(),
+            flight_number: None,
+            size: None,
+            role: None,
+            priority: None,
+            aircraft: None,
+            squadron: None,
+            task: None,
+            tot: None,
+            window: None,
+            notes: vec![],
+        }
+    }
 
-        Self(threat)
+    /// Puts a cell of the flight number, role, aircraft or task column where it belongs.
+    fn fill(
+        &mut self,
+        column: usize,
+        value: &'a str,
+        line: usize,
+        dictionary: &Dictionary,
+        diagnostics: &mut Diagnostics,
+    ) {
+        let section = SectionKind::PackageElements;
+
+        match column {
+            0 => {
+                if let Some(size) = dictionary.strip_suffix(Label::Ship, value) {
+                    self.size = parse_column(size, "flight size", section, line, diagnostics);
+                } else if self.flight_number.is_none() {
+                    self.flight_number =
+                        parse_column(value, "flight number", section, line, diagnostics);
+                } else {
+                    self.notes.push(value.into());
+                }
+            }
+            1 if self.role.is_none() => self.role = Some(value.into()),
+            1 if self.priority.is_none() => self.priority = Some(value.into()),
+            2 if self.aircraft.is_none() => self.aircraft = Some(value.into()),
+            2 if self.squadron.is_none() => self.squadron = Some(value.into()),
+            3 => {
+                if let Some(tot) = dictionary.strip_prefix(Label::Tot, value) {
+                    self.tot = parse_column(tot, "time on target", section, line, diagnostics);
+                } else if let Ok(window) = value.parse() {
+                    self.window = Some(window);
+                } else if self.task.is_none() {
+                    self.task = Some(value.into());
+                } else {
+                    self.notes.push(value.into());
+                }
+            }
+            _ => self.notes.push(value.into()),
+        }
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Steerpoint<'a> {
     pub steerpoint: usize,
-    pub description: Option<&'a str>,
-    pub time: Option<&'a str>,
+    pub description: Option<Cow<'a, str>>,
+    pub time: Option<MissionTime>,
     pub distance: Option<f64>,
     pub heading: Option<usize>,
     pub cas: Option<usize>,
-    pub altitude: Option<&'a str>,
-    pub action: Option<&'a str>,
-    pub form: Option<&'a str>,
-    pub comments: Option<&'a str>,
+    pub altitude: Option<Altitude>,
+    pub action: Option<Cow<'a, str>>,
+    pub form: Option<Cow<'a, str>>,
+    pub comments: Option<Cow<'a, str>>,
 }
 
 impl<'a> Steerpoint<'a> {
-    pub fn from_briefing(briefing: &'a str) -> Vec<Self> {
-        let Some(steerpoints) = extract_group(briefing, "Steerpoints") else {
-            return Default::default();
-        };
+    pub fn into_owned(self) -> Steerpoint<'static> {
+        Steerpoint {
+            steerpoint: self.steerpoint,
+            description: self.description.map(owned),
+            time: self.time,
+            distance: self.distance,
+            heading: self.heading,
+            cas: self.cas,
+            altitude: self.altitude,
+            action: self.action.map(owned),
+            form: self.form.map(owned),
+            comments: self.comments.map(owned),
+        }
+    }
 
-        let lines: Vec<&str> = steerpoints
+    fn from_section(
+        section: &Section<'a>,
+        layout: &Layout,
+        diagnostics: &mut Diagnostics,
+    ) -> Vec<Self> {
+        let lines: Vec<(usize, &str)> = section
             .lines()
-            .skip(4)
-            .map(str::trim)
-            .filter(|l| !l.is_empty())
+            .skip(layout.table_start)
+            .map(|(n, l)| (n, l.trim()))
+            .filter(|(_, l)| !l.is_empty())
             .collect();
 
         let mut steerpoints = vec![];
-        for line in lines {
-            let mut values = line.split('\t').map(str::trim).filter(|l| !l.is_empty());
-            let Some(index) = values.next() else { continue };
-            let index: usize = index.parse().unwrap();
-
-            let Some(description) = values.next() else {
-                continue;
-            };
-            let description = to_option(description);
-
-            let Some(time) = values.next() else { continue };
-            let time = to_option(time);
-
-            let Some(distance) = values.next() else {
+        let mut previous_time = None;
+        for (line, text) in lines {
+            let values: Vec<&str> = text
+                .split('\t')
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .collect();
+
+            let columns = layout.steerpoint_columns.len();
+            if values.len() < columns {
+                diagnostics.error(
+                    SectionKind::Steerpoints,
+                    Some(line),
+                    format!("expected {} columns, found {}", columns, values.len()),
+                );
                 continue;
-            };
-            let distance: Option<f64> = distance.parse().ok();
+            }
 
-            let Some(heading) = values.next() else {
+            // Columns the layout doesn't have read as `--`.
+            let cell = |column| layout.steerpoint_column(column).map_or("--", |i| values[i]);
+            let index = cell(SteerpointColumn::Number);
+            let description = cell(SteerpointColumn::Description);
+            let time = cell(SteerpointColumn::Time);
+            let distance = cell(SteerpointColumn::Distance);
+            let heading = cell(SteerpointColumn::Heading);
+            let cas = cell(SteerpointColumn::Cas);
+            let altitude = cell(SteerpointColumn::Altitude);
+            let action = cell(SteerpointColumn::Action);
+            let form = cell(SteerpointColumn::Form);
+            let comments = cell(SteerpointColumn::Comments);
+
+            let Ok(index) = index.parse::<usize>() else {
+                diagnostics.error(
+                    SectionKind::Steerpoints,
+                    Some(line),
+                    format!("invalid steerpoint number `{}`", index),
+                );
                 continue;
             };
-            let heading: Option<usize> = heading.parse().ok();
 
-            let Some(cas) = values.next() else { continue };
-            let cas: Option<usize> = cas.parse().ok();
-            let Some(altitude) = values.next() else {
-                continue;
-            };
-            let altitude = to_option(altitude);
-            let Some(action) = values.next() else {
-                continue;
-            };
-            let action = to_option(action);
-            let Some(form) = values.next() else { continue };
-            let form = to_option(form);
-            let Some(comments) = values.next() else {
-                continue;
-            };
-            let comments = to_option(comments);
+            // Times only go forward, anything earlier than the last one is past midnight.
+            let time: Option<MissionTime> =
+                parse_column(time, "time", SectionKind::Steerpoints, line, diagnostics).map(
+                    |t: MissionTime| match &previous_time {
+                        Some(previous) => t.after(previous),
+                        None => t,
+                    },
+                );
+            previous_time = time.clone().or(previous_time);
+            let distance: Option<f64> = parse_column(
+                distance,
+                "distance",
+                SectionKind::Steerpoints,
+                line,
+                diagnostics,
+            );
+            let heading: Option<usize> = parse_column(
+                heading,
+                "heading",
+                SectionKind::Steerpoints,
+                line,
+                diagnostics,
+            );
+            let cas: Option<usize> =
+                parse_column(cas, "CAS", SectionKind::Steerpoints, line, diagnostics);
+            let altitude: Option<Altitude> = parse_column(
+                altitude,
+                "altitude",
+                SectionKind::Steerpoints,
+                line,
+                diagnostics,
+            );
 
             steerpoints.push(Self {
                 steerpoint: index,
-                description,
+                description: to_option(description).map(Cow::from),
                 time,
                 distance,
                 heading,
                 cas,
                 altitude,
-                action,
-                form,
-                comments,
+                action: to_option(action).map(Cow::from),
+                form: to_option(form).map(Cow::from),
+                comments: to_option(comments).map(Cow::from),
             });
         }
 
@@ -323,56 +745,78 @@ impl<'a> Steerpoint<'a> {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Comm<'a> {
-    pub agency: &'a str,
-
-    pub callsign: Option<&'a str>,
-    pub uhf: Option<&'a str>,
-    pub vhf: Option<&'a str>,
-    pub notes: Option<&'a str>,
+    pub agency: Cow<'a, str>,
+
+    /// The callsign up to the first punctuation, `Kunsan Tower [11]` becomes `Kunsan Tower`.
+    pub callsign: Option<Cow<'a, str>>,
+    /// The callsign as printed, what the briefing is written back out with.
+    #[serde(default)]
+    pub raw_callsign: Option<Cow<'a, str>>,
+    pub uhf: Option<Frequency>,
+    pub vhf: Option<Frequency>,
+    pub notes: Option<Cow<'a, str>>,
 }
 
 impl<'a> Comm<'a> {
-    pub fn from_briefing(briefing: &'a str) -> Vec<Self> {
-        let Some(commladder) = extract_group(briefing, "Comm Ladder") else {
-            return Default::default();
-        };
+    pub fn into_owned(self) -> Comm<'static> {
+        Comm {
+            agency: owned(self.agency),
+            callsign: self.callsign.map(owned),
+            raw_callsign: self.raw_callsign.map(owned),
+            uhf: self.uhf,
+            vhf: self.vhf,
+            notes: self.notes.map(owned),
+        }
+    }
 
-        let lines: Vec<&str> = commladder.lines().skip(4).collect();
+    pub fn frequencies(&self) -> impl Iterator<Item = Frequency> {
+        self.uhf.into_iter().chain(self.vhf)
+    }
 
+    fn from_section(
+        section: &Section<'a>,
+        layout: &Layout,
+        diagnostics: &mut Diagnostics,
+    ) -> Vec<Self> {
         let mut commladder = vec![];
-        for line in lines {
-            let mut values = line.split('\t').map(str::trim).filter(|l| !l.is_empty());
-            let Some(agency) = values.next() else {
-                continue;
-            };
-            let Some(callsign) = values.next() else {
+        for (line, text) in section.lines().skip(layout.table_start) {
+            let values: Vec<&str> = text
+                .split('\t')
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .collect();
+
+            if values.is_empty() {
                 continue;
-            };
-            let callsign = to_option(callsign);
-
-            let Some(uhf) = values.next() else {
-                continue;
-            };
-            let uhf = to_option(uhf);
+            }
 
-            let Some(vhf) = values.next() else {
+            let [agency, callsign, uhf, vhf, notes, ..] = values[..] else {
+                diagnostics.error(
+                    SectionKind::CommLadder,
+                    Some(line),
+                    format!("expected 5 columns, found {}", values.len()),
+                );
                 continue;
             };
-            let vhf = to_option(vhf);
 
-            let Some(notes) = values.next() else {
-                continue;
+            let mut frequency = |text: &str, band: Band| {
+                let text = to_option(text)?;
+                Frequency::parse(text, band)
+                    .map_err(|e| {
+                        diagnostics.warning(SectionKind::CommLadder, Some(line), e.to_string())
+                    })
+                    .ok()
             };
-            let notes = to_option(notes);
 
             commladder.push(Self {
-                agency: agency.trim_matches(|f| f == ':'),
-                callsign,
-                uhf,
-                vhf,
-                notes,
+                agency: agency.trim_matches(|f| f == ':').into(),
+                callsign: to_option(callsign).map(clean_callsign).map(Cow::from),
+                raw_callsign: to_option(callsign).map(Cow::from),
+                uhf: frequency(uhf, Band::Uhf),
+                vhf: frequency(vhf, Band::Vhf),
+                notes: to_option(notes).map(Cow::from),
             });
         }
 
@@ -380,265 +824,57 @@ impl<'a> Comm<'a> {
     }
 }
 
-#[derive(Default, Debug, Clone, Serialize)]
-pub struct IffInitial<'a> {
-    pub modes_active: &'a str,
-    pub codes: Vec<&'a str>,
-    pub m4_validity_time_until: Vec<&'a str>,
-    pub iff_policy: Vec<&'a str>, // M1, M2, M3
-    pub code_change_setting: &'a str,
-}
-
-impl<'a> IffInitial<'a> {
-    fn from_iff(iff: &'a str) -> Self {
-        let lines: Vec<&str> = iff.lines().skip(3).take(4).collect();
-        let modes_active = lines
-            .first()
-            .unwrap_or(&"")
-            .split('\t')
-            .map(str::trim)
-            .filter(|l| !l.is_empty())
-            .nth(1)
-            .unwrap_or("")
-            .splitn(2, ':')
-            .last()
-            .unwrap_or("")
-            .trim();
-
-        let codes = lines
-            .first()
-            .unwrap_or(&"")
-            .splitn(5, '\t')
-            .map(str::trim)
-            .filter(|l| !l.is_empty())
-            .nth(3)
-            .unwrap_or("")
-            .split('\t')
-            .map(str::trim)
-            .collect::<Vec<&'a str>>();
-
-        let m4_validity_time_until = lines
-            .get(1)
-            .unwrap_or(&"")
-            .split('\t')
-            .map(str::trim)
-            .filter(|l| !l.is_empty())
-            .skip(1)
-            .collect::<Vec<&'a str>>();
-
-        let iff_policy = lines
-            .get(2)
-            .unwrap_or(&"")
-            .split('\t')
-            .map(str::trim)
-            .filter(|l| !l.is_empty())
-            .skip(1)
-            .collect::<Vec<&'a str>>();
-
-        let code_change_setting = lines
-            .get(3)
-            .unwrap_or(&"")
-            .split('\t')
-            .map(str::trim)
-            .filter(|l| !l.is_empty())
-            .nth(1)
-            .unwrap_or("");
-
-        Self {
-            modes_active,
-            codes,
-            m4_validity_time_until,
-            iff_policy,
-            code_change_setting,
-        }
-    }
-}
-
-#[derive(Default, Debug, Clone, Serialize)]
-pub struct Iff<'a> {
-    pub initial: IffInitial<'a>,
-    pub time_events: Vec<Vec<&'a str>>,
-    pub pos_events: Vec<&'a str>,
-}
-
-impl<'a> Iff<'a> {
-    pub fn from_briefing(briefing: &'a str) -> Self {
-        let Some(iff) = extract_group(briefing, "Iff") else {
-            return Default::default();
-        };
-        let initial = IffInitial::from_iff(iff);
-
-        let time_events = iff
-            .lines()
-            .skip(9)
-            .take(5)
-            .map(str::trim)
-            .filter(|l| !l.is_empty())
-            .map(|l| l.split('\t').collect::<Vec<&'a str>>())
-            .collect::<Vec<Vec<&'a str>>>();
-
-        let pos_events = iff
-            .lines()
-            .last()
-            .unwrap_or("")
-            .split('\t')
-            .map(str::trim)
-            .filter(|l| !l.is_empty())
-            .collect::<Vec<&'a str>>();
-
-        Self {
-            initial,
-            time_events,
-            pos_events,
-        }
-    }
-}
-
-#[derive(Default, Debug, Clone, Serialize)]
-pub struct Ordnance<'a> {
-    pub flights: Vec<Vec<Vec<&'a str>>>,
-}
-
-impl<'a> Ordnance<'a> {
-    pub fn from_briefing(briefing: &'a str) -> Self {
-        let mut flights = vec![];
-
-        let Some(ordnance) = extract_group(briefing, "Ordnance") else {
-            return Default::default();
-        };
-
-        let lines = ordnance.lines().skip(3).map(str::trim);
-
-        let mut flight: Option<Vec<Vec<&'a str>>> = None;
-        for line in lines {
-            if line.is_empty() {
-                // flush
-                if let Some(fl) = flight {
-                    flights.push(fl);
-
-                    flight = None;
-                }
-                continue;
-            }
-
-            if flight.is_none() {
-                let mut l = line
-                    .split('\t')
-                    .map(|l| l.trim_matches(|c: char| c == '-' || c.is_whitespace()))
-                    .skip(1);
-
-                let mut map = Vec::new();
-
-                for callsign in l.by_ref() {
-                    map.push(vec![callsign]);
-                }
-
-                flight = Some(map);
-                continue;
-            }
-
-            let l = line.split('\t').map(str::trim);
-
-            for (i, ord) in l.enumerate() {
-                if let Some(map) = flight.as_mut() {
-                    map[i].push(ord);
-                }
-            }
-        }
-
-        // flush the last group
-        if let Some(fl) = flight {
-            flights.push(fl);
-        }
-
-        Self { flights }
-    }
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct RulesOfEngagement {
+    pub text: String,
 }
 
-#[derive(Default, Debug, Clone, Serialize)]
-pub struct Weather<'a>(pub Vec<Vec<&'a str>>);
-
-impl<'a> Weather<'a> {
-    pub fn from_briefing(briefing: &'a str) -> Self {
-        let Some(weather) = extract_group(briefing, "Weather") else {
-            return Default::default();
-        };
-
-        let weather: Vec<Vec<&str>> = weather
+impl RulesOfEngagement {
+    fn from_section(roe: &Section, layout: &Layout) -> Self {
+        let lines: Vec<&str> = roe
+            .text
             .lines()
-            .skip(2)
+            .skip(layout.text_start)
             .map(str::trim)
             .filter(|&l| !l.is_empty())
-            .map(|l| l.split('\t').map(str::trim).collect::<Vec<_>>())
             .collect();
 
-        Self(weather)
-    }
-}
-
-// Support
-#[derive(Default, Debug, Clone, Serialize)]
-pub struct Support<'a>(pub Vec<Vec<&'a str>>);
-
-impl<'a> Support<'a> {
-    pub fn from_briefing(briefing: &'a str) -> Self {
-        let Some(support) = extract_group(briefing, "Support") else {
-            return Default::default();
-        };
-
-        let support: Vec<Vec<&str>> = support
-            .lines()
-            .skip(4)
-            .map(str::trim)
-            .filter(|&l| !l.is_empty())
-            .map(|l| {
-                l.split('\t')
-                    .map(|l| l.trim_matches(|c: char| c.is_whitespace() || c == ':'))
-                    .collect::<Vec<_>>()
-            })
-            .collect();
+        let roe = lines.join("\n");
 
-        Self(support)
+        Self { text: roe }
     }
 }
 
-#[derive(Default, Debug, Clone, Serialize)]
-pub struct RulesOfEngagement(pub String);
-
-impl RulesOfEngagement {
-    pub fn from_briefing(briefing: &str) -> Self {
-        let Some(roe) = extract_group(briefing, "Rules of Engagement") else {
-            return Default::default();
-        };
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct Emergency {
+    pub text: String,
+}
 
-        let lines: Vec<&str> = roe
+impl Emergency {
+    fn from_section(emergency: &Section, layout: &Layout) -> Self {
+        let lines: Vec<&str> = emergency
+            .text
             .lines()
-            .skip(2)
+            .skip(layout.text_start)
             .map(str::trim)
-            .filter(|&l| !l.is_empty())
             .collect();
 
-        let roe = lines.join("\n");
+        let emergency = lines.join("\n");
 
-        Self(roe)
+        Self { text: emergency }
     }
 }
 
-#[derive(Default, Debug, Clone, Serialize)]
-pub struct Emergency(pub String);
-
-impl Emergency {
-    pub fn from_briefing(briefing: &str) -> Self {
-        let Some(emergency) = extract_group(briefing, "Emergency Procedures") else {
-            return Default::default();
-        };
-
-        let lines: Vec<&str> = emergency.lines().skip(2).map(str::trim).collect();
-
-        let emergency = lines.join("\n");
+fn clean_callsign(callsign: &str) -> &str {
+    callsign
+        .split(|c: char| !c.is_alphanumeric() && !c.is_whitespace() && c != '-')
+        .next()
+        .unwrap_or(callsign)
+        .trim()
+}
 
-        Self(emergency)
-    }
+fn owned(text: Cow<str>) -> Cow<'static, str> {
+    Cow::Owned(text.into_owned())
 }
 
 fn to_option(str: &str) -> Option<&str> {
@@ -648,30 +884,60 @@ fn to_option(str: &str) -> Option<&str> {
     Some(str)
 }
 
-fn extract_group<'a>(text: &'a str, group_name: &str) -> Option<&'a str> {
-    let mut current_line_start = 0;
-    let mut current_group_start = 0;
-
-    for (i, (_, c)) in text.char_indices().enumerate() {
-        if current_line_start > i {
-            continue;
+/// Runs `parse` on the section if the briefing has one, reporting it missing otherwise.
+fn section<'a, T: Default>(
+    index: &SectionIndex<'a>,
+    kind: SectionKind,
+    diagnostics: &mut Diagnostics,
+    parse: impl FnOnce(&Section<'a>, &mut Diagnostics) -> T,
+) -> T {
+    match index.get(kind) {
+        Some(section) => parse(section, diagnostics),
+        None => {
+            diagnostics.missing(kind);
+            T::default()
         }
+    }
+}
 
-        if !text[current_line_start..i].starts_with('\t')
-            && text[current_line_start..i + 1].ends_with('\n')
-            && !text[current_line_start..i + 1].trim().is_empty()
-        {
-            if text[current_line_start..i].starts_with(group_name) {
-                current_group_start = current_line_start;
-            } else if current_group_start != 0 {
-                return Some(&text[current_group_start..current_line_start - 1]);
-            }
-        }
+/// Like [`section`], with the layout the section is printed in. Skipped when
+/// it isn't laid out like any known release.
+fn table<'a, T: Default>(
+    index: &SectionIndex<'a>,
+    kind: SectionKind,
+    diagnostics: &mut Diagnostics,
+    parse: impl FnOnce(&Section<'a>, &Layout, &mut Diagnostics) -> T,
+) -> T {
+    section(index, kind, diagnostics, |s, d| {
+        let Some(version) = BmsFormatVersion::of_section(s) else {
+            d.error(
+                kind,
+                None,
+                "unsupported BMS version, the section isn't laid out like any known release",
+            );
+            return T::default();
+        };
 
-        if c == '\n' {
-            current_line_start = i + 1;
-        }
-    }
+        parse(s, Layout::of(version), d)
+    })
+}
 
-    None
+/// Parses a column into `T`, warning about anything that isn't a valid value or a placeholder.
+fn parse_column<T: FromStr>(
+    value: &str,
+    column: &str,
+    section: SectionKind,
+    line: usize,
+    diagnostics: &mut Diagnostics,
+) -> Option<T> {
+    let value = to_option(value)?;
+    let parsed = value.parse().ok();
+    if parsed.is_none() {
+        diagnostics.warning(
+            section,
+            Some(line),
+            format!("invalid {} `{}`", column, value),
+        );
+    }
+    parsed
 }
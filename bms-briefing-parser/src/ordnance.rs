@@ -0,0 +1,191 @@
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{diagnostic::Diagnostics, Layout, Section, SectionKind};
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct Ordnance<'a> {
+    pub flights: Vec<OrdnanceFlight<'a>>,
+}
+
+impl<'a> Ordnance<'a> {
+    pub fn into_owned(self) -> Ordnance<'static> {
+        Ordnance {
+            flights: self
+                .flights
+                .into_iter()
+                .map(OrdnanceFlight::into_owned)
+                .collect(),
+        }
+    }
+
+    pub fn flight(&self, callsign: &str) -> Option<&OrdnanceFlight<'a>> {
+        self.flights.iter().find(|f| f.callsign == callsign)
+    }
+
+    pub(crate) fn from_section(
+        ordnance: &Section<'a>,
+        layout: &Layout,
+        diagnostics: &mut Diagnostics,
+    ) -> Self {
+        let mut flights = vec![];
+
+        // Columns line up with the aircraft, so only the indentation is stripped.
+        let lines = ordnance
+            .lines()
+            .skip(layout.ordnance_start)
+            .map(|(n, l)| (n, l.strip_prefix('\t').unwrap_or(l).trim_end()));
+
+        let mut flight: Option<OrdnanceFlight<'a>> = None;
+        for (number, line) in lines {
+            if line.trim().is_empty() {
+                // flush
+                if let Some(fl) = flight.take() {
+                    flights.push(fl);
+                }
+                continue;
+            }
+
+            let Some(current) = flight.as_mut() else {
+                flight = Some(OrdnanceFlight::from_header(line));
+                continue;
+            };
+
+            for (i, store) in line.split('\t').map(str::trim).enumerate() {
+                let Some(aircraft) = current.aircraft.get_mut(i) else {
+                    diagnostics.error(
+                        SectionKind::Ordnance,
+                        Some(number),
+                        format!("more columns than aircraft, `{}` dropped", store),
+                    );
+                    break;
+                };
+
+                if !store.is_empty() {
+                    aircraft.stations.push(Store::parse(store));
+                }
+            }
+        }
+
+        // flush the last group
+        if let Some(fl) = flight {
+            flights.push(fl);
+        }
+
+        Self { flights }
+    }
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct OrdnanceFlight<'a> {
+    pub callsign: Cow<'a, str>,
+    pub aircraft: Vec<AircraftLoadout<'a>>,
+}
+
+impl<'a> OrdnanceFlight<'a> {
+    /// `Viper1  --Viper11--  --Viper12--`
+    fn from_header(line: &'a str) -> Self {
+        let mut columns = line
+            .split('\t')
+            .map(|l| l.trim_matches(|c: char| c == '-' || c.is_whitespace()));
+
+        let callsign = columns.next().unwrap_or_default();
+        let aircraft = columns
+            .enumerate()
+            .map(|(i, callsign)| AircraftLoadout {
+                seat: i + 1,
+                callsign: callsign.into(),
+                stations: vec![],
+            })
+            .collect();
+
+        Self {
+            callsign: callsign.into(),
+            aircraft,
+        }
+    }
+
+    pub fn into_owned(self) -> OrdnanceFlight<'static> {
+        OrdnanceFlight {
+            callsign: crate::owned(self.callsign),
+            aircraft: self
+                .aircraft
+                .into_iter()
+                .map(AircraftLoadout::into_owned)
+                .collect(),
+        }
+    }
+
+    /// Every store carried by the flight, summed over its aircraft.
+    pub fn totals(&self) -> Vec<Store<'a>> {
+        let mut totals: Vec<Store<'a>> = vec![];
+        for store in self.aircraft.iter().flat_map(|a| &a.stations) {
+            match totals.iter_mut().find(|t| t.name == store.name) {
+                Some(total) => total.quantity = total.quantity.saturating_add(store.quantity),
+                None => totals.push(store.clone()),
+            }
+        }
+        totals
+    }
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct AircraftLoadout<'a> {
+    /// Position in the flight, 1 for the flight lead.
+    pub seat: usize,
+    pub callsign: Cow<'a, str>,
+    pub stations: Vec<Store<'a>>,
+}
+
+impl AircraftLoadout<'_> {
+    pub fn into_owned(self) -> AircraftLoadout<'static> {
+        AircraftLoadout {
+            seat: self.seat,
+            callsign: crate::owned(self.callsign),
+            stations: self.stations.into_iter().map(Store::into_owned).collect(),
+        }
+    }
+}
+
+/// Units in store names such as `600 Gal Tank` or `2000 lb`.
+const UNITS: [&str; 5] = ["gal", "gallon", "lb", "lbs", "kg"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Store<'a> {
+    pub name: Cow<'a, str>,
+    pub quantity: u32,
+}
+
+impl<'a> Store<'a> {
+    pub fn into_owned(self) -> Store<'static> {
+        Store {
+            name: crate::owned(self.name),
+            quantity: self.quantity,
+        }
+    }
+
+    /// `2 AIM-120C`, a store without a count is a single one.
+    ///
+    /// A number followed by a unit is part of the name, `600 Gal Tank` is one tank.
+    fn parse(text: &'a str) -> Self {
+        let counted = text.split_once(' ').and_then(|(quantity, name)| {
+            let quantity = quantity.trim_end_matches('x').parse().ok()?;
+            let name = name.trim();
+            let unit = name.split(' ').next().unwrap_or_default();
+            if UNITS.iter().any(|u| u.eq_ignore_ascii_case(unit)) {
+                return None;
+            }
+
+            Some(Self {
+                name: name.into(),
+                quantity,
+            })
+        });
+
+        counted.unwrap_or(Self {
+            name: text.into(),
+            quantity: 1,
+        })
+    }
+}
@@ -0,0 +1,187 @@
+use std::{collections::HashMap, fmt, ops::Range};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Dictionary;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SectionKind {
+    Overview,
+    Situation,
+    PilotRoster,
+    PackageElements,
+    ThreatAnalysis,
+    Steerpoints,
+    CommLadder,
+    Iff,
+    Ordnance,
+    Weather,
+    Support,
+    RulesOfEngagement,
+    EmergencyProcedures,
+}
+
+impl SectionKind {
+    pub const ALL: [SectionKind; 13] = [
+        SectionKind::Overview,
+        SectionKind::Situation,
+        SectionKind::PilotRoster,
+        SectionKind::PackageElements,
+        SectionKind::ThreatAnalysis,
+        SectionKind::Steerpoints,
+        SectionKind::CommLadder,
+        SectionKind::Iff,
+        SectionKind::Ordnance,
+        SectionKind::Weather,
+        SectionKind::Support,
+        SectionKind::RulesOfEngagement,
+        SectionKind::EmergencyProcedures,
+    ];
+
+    /// The start of the header line BMS prints for this section in English.
+    pub fn header(self) -> &'static str {
+        match self {
+            SectionKind::Overview => "Mission Overview",
+            SectionKind::Situation => "Situation",
+            SectionKind::PilotRoster => "Pilot Roster",
+            SectionKind::PackageElements => "Package Elements",
+            SectionKind::ThreatAnalysis => "Threat Analysis",
+            SectionKind::Steerpoints => "Steerpoints",
+            SectionKind::CommLadder => "Comm Ladder",
+            SectionKind::Iff => "Iff",
+            SectionKind::Ordnance => "Ordnance",
+            SectionKind::Weather => "Weather",
+            SectionKind::Support => "Support",
+            SectionKind::RulesOfEngagement => "Rules of Engagement",
+            SectionKind::EmergencyProcedures => "Emergency Procedures",
+        }
+    }
+
+    /// The name of the section in JSON and in dictionary tables.
+    pub fn key(self) -> &'static str {
+        match self {
+            SectionKind::Overview => "overview",
+            SectionKind::Situation => "situation",
+            SectionKind::PilotRoster => "pilot_roster",
+            SectionKind::PackageElements => "package_elements",
+            SectionKind::ThreatAnalysis => "threat_analysis",
+            SectionKind::Steerpoints => "steerpoints",
+            SectionKind::CommLadder => "comm_ladder",
+            SectionKind::Iff => "iff",
+            SectionKind::Ordnance => "ordnance",
+            SectionKind::Weather => "weather",
+            SectionKind::Support => "support",
+            SectionKind::RulesOfEngagement => "rules_of_engagement",
+            SectionKind::EmergencyProcedures => "emergency_procedures",
+        }
+    }
+}
+
+impl fmt::Display for SectionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.header())
+    }
+}
+
+/// A group of lines in the briefing, starting with its header line.
+#[derive(Debug, Clone)]
+pub struct Section<'a> {
+    /// `None` for headers that don't belong to a known section.
+    pub kind: Option<SectionKind>,
+    pub header: &'a str,
+    pub text: &'a str,
+    /// Byte range of `text` in the briefing.
+    pub range: Range<usize>,
+    /// 1-based line number of the header in the briefing.
+    pub line: usize,
+}
+
+impl<'a> Section<'a> {
+    /// The lines of the section, paired with their line number in the briefing.
+    pub fn lines(&self) -> impl Iterator<Item = (usize, &'a str)> {
+        let start = self.line;
+        self.text
+            .lines()
+            .enumerate()
+            .map(move |(i, l)| (start + i, l))
+    }
+}
+
+/// Every section of a briefing, found in a single pass over the text.
+///
+/// A header is any non-blank line that doesn't start with a tab, everything up
+/// to the next header belongs to it.
+#[derive(Debug, Clone, Default)]
+pub struct SectionIndex<'a> {
+    sections: Vec<Section<'a>>,
+    kinds: HashMap<SectionKind, usize>,
+}
+
+impl<'a> SectionIndex<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self::with_dictionary(text, Dictionary::english())
+    }
+
+    /// Like [`SectionIndex::new`], recognizing headers in the dictionary's language.
+    pub fn with_dictionary(text: &'a str, dictionary: &Dictionary) -> Self {
+        let mut index = Self::default();
+
+        let mut open: Option<(usize, usize, &'a str)> = None;
+        let mut offset = 0;
+
+        for (i, line) in text.split_inclusive('\n').enumerate() {
+            let content = line.trim_end_matches(['\r', '\n']);
+
+            if !content.starts_with('\t') && !content.trim().is_empty() {
+                if let Some((start, number, header)) = open.take() {
+                    index.push(text, start..offset, number, header, dictionary);
+                }
+                open = Some((offset, i + 1, content.trim()));
+            }
+
+            offset += line.len();
+        }
+
+        if let Some((start, number, header)) = open {
+            index.push(text, start..text.len(), number, header, dictionary);
+        }
+
+        index
+    }
+
+    /// The first section with the given kind.
+    pub fn get(&self, kind: SectionKind) -> Option<&Section<'a>> {
+        self.kinds.get(&kind).map(|&i| &self.sections[i])
+    }
+
+    /// All sections in the order they appear, including unknown ones.
+    pub fn sections(&self) -> &[Section<'a>] {
+        &self.sections
+    }
+
+    fn push(
+        &mut self,
+        text: &'a str,
+        range: Range<usize>,
+        line: usize,
+        header: &'a str,
+        dictionary: &Dictionary,
+    ) {
+        let end = range.start + text[range.clone()].trim_end_matches(['\r', '\n']).len();
+        let range = range.start..end;
+
+        let kind = dictionary.section(header);
+        if let Some(kind) = kind {
+            self.kinds.entry(kind).or_insert(self.sections.len());
+        }
+
+        self.sections.push(Section {
+            kind,
+            header,
+            text: &text[range.clone()],
+            range,
+            line,
+        });
+    }
+}
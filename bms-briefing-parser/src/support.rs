@@ -0,0 +1,190 @@
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    diagnostic::Diagnostics, Band, Comm, Dictionary, Frequency, InvalidValue, Label, Layout,
+    Section, SectionKind, Tacan, TimeWindow,
+};
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct Support<'a> {
+    pub assets: Vec<SupportAsset<'a>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SupportRole {
+    Tanker,
+    Awacs,
+    Jstars,
+    Cap,
+    SeadEscort,
+    Other,
+}
+
+impl SupportRole {
+    fn from_kind(kind: &str) -> Self {
+        match kind.to_ascii_uppercase().as_str() {
+            "TANKER" => SupportRole::Tanker,
+            "AWACS" => SupportRole::Awacs,
+            "JSTARS" => SupportRole::Jstars,
+            "CAP" | "BARCAP" | "HAVCAP" => SupportRole::Cap,
+            "SEAD" | "SEAD ESCORT" => SupportRole::SeadEscort,
+            _ => SupportRole::Other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupportAsset<'a> {
+    pub role: SupportRole,
+    /// The type column as printed, `Tanker`, `AWACS`, ...
+    pub kind: Cow<'a, str>,
+    pub callsign: Cow<'a, str>,
+    pub aircraft: Option<Cow<'a, str>>,
+    pub frequency: Option<Frequency>,
+    pub tacan: Option<Tacan>,
+    /// Where the asset orbits, `STPT 8`.
+    pub track: Option<Cow<'a, str>>,
+    pub on_station: Option<TimeWindow>,
+    /// Parts of the description that aren't understood.
+    pub notes: Vec<Cow<'a, str>>,
+}
+
+impl<'a> Support<'a> {
+    pub fn into_owned(self) -> Support<'static> {
+        Support {
+            assets: self
+                .assets
+                .into_iter()
+                .map(SupportAsset::into_owned)
+                .collect(),
+        }
+    }
+
+    pub fn tankers(&self) -> impl Iterator<Item = &SupportAsset<'a>> {
+        self.assets.iter().filter(|a| a.role == SupportRole::Tanker)
+    }
+
+    pub(crate) fn from_section(
+        support: &Section<'a>,
+        layout: &Layout,
+        dictionary: &Dictionary,
+        diagnostics: &mut Diagnostics,
+    ) -> Self {
+        let mut assets: Vec<SupportAsset<'a>> = vec![];
+
+        let lines = support
+            .lines()
+            .skip(layout.table_start)
+            .map(|(n, l)| (n, l.trim()))
+            .filter(|(_, l)| !l.is_empty());
+
+        for (line, text) in lines {
+            let values: Vec<&str> = text
+                .split('\t')
+                .map(|l| l.trim_matches(|c: char| c.is_whitespace() || c == ':'))
+                .collect();
+
+            let mut warn = |e: InvalidValue| {
+                diagnostics.warning(SectionKind::Support, Some(line), e.to_string())
+            };
+
+            match values[..] {
+                [callsign, kind, ref description @ ..] => {
+                    let mut asset = SupportAsset {
+                        role: SupportRole::from_kind(kind),
+                        kind: kind.into(),
+                        callsign: callsign.into(),
+                        aircraft: None,
+                        frequency: None,
+                        tacan: None,
+                        track: None,
+                        on_station: None,
+                        notes: vec![],
+                    };
+
+                    let mut parts = description.iter().flat_map(|d| d.split(", "));
+                    asset.aircraft = parts.next().filter(|a| !a.is_empty()).map(Cow::from);
+                    for part in parts {
+                        asset
+                            .describe(part.trim(), dictionary)
+                            .unwrap_or_else(&mut warn);
+                    }
+
+                    assets.push(asset);
+                }
+                // Lines after an asset continue its description.
+                [description] => match assets.last_mut() {
+                    Some(asset) => {
+                        for part in description.split(", ") {
+                            asset
+                                .describe(part.trim(), dictionary)
+                                .unwrap_or_else(&mut warn);
+                        }
+                    }
+                    None => diagnostics.warning(
+                        SectionKind::Support,
+                        Some(line),
+                        format!("description without an asset `{}`", description),
+                    ),
+                },
+                [] => {}
+            }
+        }
+
+        Self { assets }
+    }
+}
+
+impl<'a> SupportAsset<'a> {
+    pub fn into_owned(self) -> SupportAsset<'static> {
+        SupportAsset {
+            role: self.role,
+            kind: crate::owned(self.kind),
+            callsign: crate::owned(self.callsign),
+            aircraft: self.aircraft.map(crate::owned),
+            frequency: self.frequency,
+            tacan: self.tacan,
+            track: self.track.map(crate::owned),
+            on_station: self.on_station,
+            notes: self.notes.into_iter().map(crate::owned).collect(),
+        }
+    }
+
+    /// The asset as a comm ladder entry, `None` without a frequency to call it on.
+    pub fn comm(&self) -> Option<Comm<'a>> {
+        let frequency = self.frequency?;
+
+        Some(Comm {
+            agency: self.kind.clone(),
+            callsign: Some(self.callsign.clone()),
+            raw_callsign: Some(self.callsign.clone()),
+            uhf: Some(frequency).filter(|f| f.band() == Band::Uhf),
+            vhf: Some(frequency).filter(|f| f.band() == Band::Vhf),
+            notes: self.track.clone(),
+        })
+    }
+
+    /// Fills in one `, `-separated part of the description.
+    fn describe(&mut self, part: &'a str, dictionary: &Dictionary) -> Result<(), InvalidValue> {
+        if let Some(uhf) = dictionary.strip_prefix(Label::Uhf, part) {
+            self.frequency = Some(Frequency::parse(uhf, Band::Uhf)?);
+        } else if let Some(vhf) = dictionary.strip_prefix(Label::Vhf, part) {
+            self.frequency = Some(Frequency::parse(vhf, Band::Vhf)?);
+        } else if let Some(tacan) = dictionary.strip_prefix(Label::Tacan, part) {
+            self.tacan = Some(tacan.parse()?);
+        } else if let Some(track) = dictionary.strip_prefix(Label::Track, part) {
+            self.track = Some(track.into());
+        } else if let Some(window) = dictionary.strip_prefix(Label::OnStation, part) {
+            self.on_station = Some(window.parse()?);
+        } else if let Ok(window) = part.parse() {
+            self.on_station = Some(window);
+        } else if !part.is_empty() {
+            self.notes.push(part.into());
+        }
+
+        Ok(())
+    }
+}
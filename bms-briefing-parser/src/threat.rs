@@ -0,0 +1,343 @@
+use std::{borrow::Cow, sync::OnceLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    diagnostic::Diagnostics, Dictionary, InvalidValue, Label, Layout, Section, SectionKind,
+};
+
+const BUNDLED: &str = include_str!("../data/threats.tsv");
+
+const DIRECTIONS: [Label; 8] = [
+    Label::North,
+    Label::South,
+    Label::East,
+    Label::West,
+    Label::Northeast,
+    Label::Northwest,
+    Label::Southeast,
+    Label::Southwest,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThreatKind {
+    Sam,
+    Aaa,
+    Manpads,
+}
+
+/// A row of the threat database.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreatSystem<'a> {
+    pub name: Cow<'a, str>,
+    pub aliases: Vec<Cow<'a, str>>,
+    pub kind: ThreatKind,
+    /// Maximum engagement range in nautical miles.
+    pub range: f64,
+    /// Lowest altitude it engages at, in feet.
+    pub min_altitude: u32,
+    /// Highest altitude it engages at, in feet.
+    pub max_altitude: u32,
+    /// `None` for systems that don't show up on the RWR.
+    pub rwr: Option<Cow<'a, str>>,
+}
+
+impl<'a> ThreatSystem<'a> {
+    fn matches(&self, name: &str) -> bool {
+        let name = normalize(name);
+        [&self.name]
+            .into_iter()
+            .chain(&self.aliases)
+            .any(|n| normalize(n) == name)
+    }
+}
+
+/// Engagement ranges and RWR symbols for the systems a briefing may mention.
+///
+/// The crate ships with one, see [`ThreatDatabase::bundled`], but a table in
+/// the same format can be loaded with [`ThreatDatabase::parse`].
+#[derive(Debug, Clone)]
+pub struct ThreatDatabase<'a> {
+    version: &'a str,
+    systems: Vec<ThreatSystem<'a>>,
+}
+
+impl<'a> ThreatDatabase<'a> {
+    /// The database in `data/threats.tsv`.
+    pub fn bundled() -> &'static ThreatDatabase<'static> {
+        static DATABASE: OnceLock<ThreatDatabase<'static>> = OnceLock::new();
+        DATABASE.get_or_init(|| ThreatDatabase::parse(BUNDLED).expect("bundled threat database"))
+    }
+
+    /// Reads a tab separated table, a `version` line followed by one system per line.
+    pub fn parse(text: &'a str) -> Result<Self, InvalidValue> {
+        let mut version = None;
+        let mut systems = vec![];
+
+        let lines = text
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'));
+
+        for line in lines {
+            let invalid = || InvalidValue::new("threat system", line);
+            let columns: Vec<&str> = line.split('\t').map(str::trim).collect();
+
+            match columns[..] {
+                ["version", v] => version = Some(v),
+                [name, aliases, kind, range, min_altitude, max_altitude, rwr] => {
+                    systems.push(ThreatSystem {
+                        name: name.into(),
+                        aliases: aliases
+                            .split(',')
+                            .filter(|a| !a.is_empty())
+                            .map(Cow::from)
+                            .collect(),
+                        kind: match kind {
+                            "sam" => ThreatKind::Sam,
+                            "aaa" => ThreatKind::Aaa,
+                            "manpads" => ThreatKind::Manpads,
+                            _ => return Err(invalid()),
+                        },
+                        range: range.parse().map_err(|_| invalid())?,
+                        min_altitude: min_altitude.parse().map_err(|_| invalid())?,
+                        max_altitude: max_altitude.parse().map_err(|_| invalid())?,
+                        rwr: crate::to_option(rwr).map(Cow::from),
+                    })
+                }
+                _ => return Err(invalid()),
+            }
+        }
+
+        Ok(Self {
+            version: version.ok_or_else(|| InvalidValue::new("threat database version", ""))?,
+            systems,
+        })
+    }
+
+    pub fn version(&self) -> &'a str {
+        self.version
+    }
+
+    pub fn systems(&self) -> &[ThreatSystem<'a>] {
+        &self.systems
+    }
+
+    /// Finds a system by name or alias, ignoring case and dashes: `sa6` finds `SA-6`.
+    pub fn get(&self, name: &str) -> Option<&ThreatSystem<'a>> {
+        self.systems.iter().find(|s| s.matches(name))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LocationReference {
+    Steerpoint(usize),
+    Target,
+    Airfield,
+    Route,
+}
+
+/// Where a threat is, as far as the briefing says: `12nm north of steerpoint 5`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThreatLocation<'a> {
+    /// Nautical miles from the reference.
+    pub distance: Option<f64>,
+    pub direction: Option<Cow<'a, str>>,
+    pub reference: Option<LocationReference>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Threat<'a> {
+    /// The system as the briefing names it.
+    pub system: Cow<'a, str>,
+    pub location: Option<ThreatLocation<'a>>,
+    /// The line the threat was mentioned on.
+    pub notes: Cow<'a, str>,
+    /// `None` when the system isn't in the database.
+    pub details: Option<Cow<'static, ThreatSystem<'static>>>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct ThreatAnalysis<'a> {
+    pub text: Cow<'a, str>,
+    pub threats: Vec<Threat<'a>>,
+    /// Lines that don't mention a known system.
+    pub remarks: Vec<Cow<'a, str>>,
+    /// Version of the threat database the threats were looked up in.
+    pub database: Cow<'a, str>,
+}
+
+impl<'a> ThreatAnalysis<'a> {
+    pub fn into_owned(self) -> ThreatAnalysis<'static> {
+        ThreatAnalysis {
+            text: crate::owned(self.text),
+            threats: self.threats.into_iter().map(Threat::into_owned).collect(),
+            remarks: self.remarks.into_iter().map(crate::owned).collect(),
+            database: crate::owned(self.database),
+        }
+    }
+
+    pub(crate) fn from_section(
+        threat: &Section<'a>,
+        layout: &Layout,
+        dictionary: &Dictionary,
+        diagnostics: &mut Diagnostics,
+    ) -> Self {
+        let database = ThreatDatabase::bundled();
+
+        let mut analysis = Self {
+            text: threat
+                .text
+                .splitn(layout.text_start + 1, '\n')
+                .last()
+                .unwrap_or("")
+                .into(),
+            database: database.version().into(),
+            ..Default::default()
+        };
+
+        let lines = threat
+            .lines()
+            .skip(layout.text_start)
+            .map(|(n, l)| (n, l.trim()))
+            .filter(|(_, l)| !l.is_empty());
+
+        for (line, text) in lines {
+            let location = ThreatLocation::find(text, dictionary);
+            let mentions_system = words(text).any(|w| dictionary.is(Label::Site, w));
+
+            let mut found = false;
+            for word in words(text) {
+                // `Hawk` or `Tor` may as well be a callsign or a word.
+                let details = database
+                    .get(word)
+                    .filter(|_| mentions_system || is_designator(word));
+                if details.is_none() {
+                    if !looks_like_system(word) {
+                        continue;
+                    }
+                    diagnostics.warning(
+                        SectionKind::ThreatAnalysis,
+                        Some(line),
+                        format!("unknown threat system `{}`", word),
+                    );
+                }
+
+                found = true;
+                analysis.threats.push(Threat {
+                    system: word.into(),
+                    location: location.clone(),
+                    notes: text.into(),
+                    details: details.map(Cow::Borrowed),
+                });
+            }
+
+            if !found {
+                analysis.remarks.push(text.into());
+            }
+        }
+
+        analysis
+    }
+}
+
+impl Threat<'_> {
+    pub fn into_owned(self) -> Threat<'static> {
+        Threat {
+            system: crate::owned(self.system),
+            location: self.location.map(ThreatLocation::into_owned),
+            notes: crate::owned(self.notes),
+            details: self.details,
+        }
+    }
+}
+
+impl<'a> ThreatLocation<'a> {
+    pub fn into_owned(self) -> ThreatLocation<'static> {
+        ThreatLocation {
+            distance: self.distance,
+            direction: self.direction.map(crate::owned),
+            reference: self.reference,
+        }
+    }
+
+    fn find(text: &'a str, dictionary: &Dictionary) -> Option<Self> {
+        let words: Vec<&str> = words(text).collect();
+        let is = |i: usize, word: &str| words.get(i).is_some_and(|w| w.eq_ignore_ascii_case(word));
+        let is_label = |i: usize, label| words.get(i).is_some_and(|w| dictionary.is(label, w));
+
+        let mut location = Self {
+            distance: None,
+            direction: None,
+            reference: None,
+        };
+
+        for (i, word) in words.iter().enumerate() {
+            if location.distance.is_none() {
+                let (number, next) = match word.strip_suffix("nm") {
+                    Some(number) => (number, i + 1),
+                    None if is(i + 1, "nm") => (*word, i + 2),
+                    None => ("", i),
+                };
+                if let Ok(distance) = number.parse() {
+                    location.distance = Some(distance);
+                    location.direction = words
+                        .get(next)
+                        .filter(|w| DIRECTIONS.iter().any(|&d| dictionary.is(d, w)))
+                        .map(|&w| w.into());
+                    continue;
+                }
+            }
+
+            if is_label(i, Label::Steerpoint) {
+                if let Some(number) = words.get(i + 1).and_then(|n| n.parse().ok()) {
+                    location.reference = Some(LocationReference::Steerpoint(number));
+                }
+            }
+        }
+
+        // A steerpoint is the most precise reference, the others are a fallback.
+        if location.reference.is_none() {
+            let references = [
+                (Label::Target, LocationReference::Target),
+                (Label::Airfield, LocationReference::Airfield),
+                (Label::Route, LocationReference::Route),
+            ];
+            location.reference = words.iter().find_map(|w| {
+                references
+                    .iter()
+                    .find(|(label, _)| dictionary.is(*label, w))
+                    .map(|&(_, reference)| reference)
+            });
+        }
+
+        let found = location.distance.is_some() || location.reference.is_some();
+        found.then_some(location)
+    }
+}
+
+fn words(text: &str) -> impl Iterator<Item = &str> {
+    text.split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric() && c != '-'))
+        .filter(|w| !w.is_empty())
+}
+
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+/// Names with a number in them, such as `SA-6`, `S-75` or `2S6`.
+fn is_designator(word: &str) -> bool {
+    word.chars().any(|c| c.is_ascii_digit())
+}
+
+/// NATO SAM designations such as `SA-21` that aren't in the database.
+fn looks_like_system(word: &str) -> bool {
+    word.strip_prefix("SA-")
+        .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+}
@@ -0,0 +1,223 @@
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+    time::Duration,
+};
+
+use serde::{ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::InvalidValue;
+
+const DAY: u32 = 24 * 60 * 60;
+
+/// A time of day in the mission, counted from midnight of the day it starts on.
+///
+/// BMS only prints `hh:mm:ss`, so times past midnight are placed on the next
+/// day by comparing them to the time before them, see [`MissionTime::after`].
+///
+/// Times compare by when they are, `7:30` and `07:30:00` are the same time.
+#[derive(Debug, Clone, Default)]
+pub struct MissionTime {
+    seconds: u32,
+    /// As printed in the briefing, `None` for times that weren't parsed.
+    text: Option<Box<str>>,
+}
+
+impl MissionTime {
+    /// `None` when the time is too far out to count in seconds.
+    pub fn from_hms(hours: u32, minutes: u32, seconds: u32) -> Option<Self> {
+        let seconds = hours
+            .checked_mul(3600)?
+            .checked_add(minutes.checked_mul(60)?)?
+            .checked_add(seconds)?;
+        Some(Self::from_seconds(seconds))
+    }
+
+    pub fn from_seconds(seconds: u32) -> Self {
+        Self {
+            seconds,
+            text: None,
+        }
+    }
+
+    /// Seconds since midnight of the first mission day.
+    pub fn as_seconds(&self) -> u32 {
+        self.seconds
+    }
+
+    /// 0 for the first mission day, 1 after the first midnight, ...
+    pub fn day(&self) -> u32 {
+        self.seconds / DAY
+    }
+
+    pub fn hours(&self) -> u32 {
+        self.seconds % DAY / 3600
+    }
+
+    pub fn minutes(&self) -> u32 {
+        self.seconds % 3600 / 60
+    }
+
+    pub fn seconds(&self) -> u32 {
+        self.seconds % 60
+    }
+
+    /// The time as it was printed in the briefing, `hh:mm:ss` when it wasn't parsed.
+    pub fn text(&self) -> Cow<'_, str> {
+        match &self.text {
+            Some(text) => Cow::Borrowed(text),
+            None => Cow::Owned(self.to_string()),
+        }
+    }
+
+    /// Moves this time forward by whole days until it is not before `previous`.
+    pub fn after(mut self, previous: &MissionTime) -> Self {
+        let behind = previous.seconds.saturating_sub(self.seconds);
+        self.seconds = self.seconds.saturating_add(behind.div_ceil(DAY) * DAY);
+        self
+    }
+
+    /// Time elapsed since `earlier`, `None` if `earlier` is later than `self`.
+    pub fn duration_since(&self, earlier: &MissionTime) -> Option<Duration> {
+        self.seconds
+            .checked_sub(earlier.seconds)
+            .map(|s| Duration::from_secs(s.into()))
+    }
+}
+
+impl PartialEq for MissionTime {
+    fn eq(&self, other: &Self) -> bool {
+        self.seconds == other.seconds
+    }
+}
+
+impl Eq for MissionTime {}
+
+impl PartialOrd for MissionTime {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MissionTime {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.seconds.cmp(&other.seconds)
+    }
+}
+
+impl Hash for MissionTime {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.seconds.hash(state);
+    }
+}
+
+impl FromStr for MissionTime {
+    type Err = InvalidValue;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidValue::new("time", s);
+
+        let mut parts = s.trim().split(':').map(|p| p.parse::<u32>());
+        let (Some(Ok(hours)), Some(Ok(minutes)), seconds, None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+        let seconds = match seconds {
+            Some(Ok(seconds)) => seconds,
+            Some(Err(_)) => return Err(invalid()),
+            None => 0,
+        };
+
+        // 24:00:00 is the end of the day, as in M4 key validity, nothing after it.
+        let end_of_day = hours == 24 && minutes == 0 && seconds == 0;
+        if (hours > 23 && !end_of_day) || minutes > 59 || seconds > 59 {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            seconds: hours * 3600 + minutes * 60 + seconds,
+            text: Some(s.trim().into()),
+        })
+    }
+}
+
+impl fmt::Display for MissionTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:02}:{:02}:{:02}",
+            self.hours(),
+            self.minutes(),
+            self.seconds()
+        )
+    }
+}
+
+impl Serialize for MissionTime {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("MissionTime", 3)?;
+        s.serialize_field("text", &self.text())?;
+        s.serialize_field("day", &self.day())?;
+        s.serialize_field("seconds", &self.seconds)?;
+        s.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for MissionTime {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Repr {
+            seconds: u32,
+            text: Option<Box<str>>,
+        }
+
+        let Repr { seconds, text } = Repr::deserialize(deserializer)?;
+        Ok(Self { seconds, text })
+    }
+}
+
+/// A stretch of mission time, such as a tanker's time on station: `07:30:00 - 09:30:00`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TimeWindow {
+    pub start: MissionTime,
+    /// Never before `start`, windows that span midnight end on the next day.
+    pub end: MissionTime,
+}
+
+impl TimeWindow {
+    pub fn contains(&self, time: &MissionTime) -> bool {
+        &self.start <= time && time <= &self.end
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.end.duration_since(&self.start).unwrap_or_default()
+    }
+}
+
+impl FromStr for TimeWindow {
+    type Err = InvalidValue;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once('-')
+            .ok_or_else(|| InvalidValue::new("time window", s))?;
+
+        let start: MissionTime = start.parse()?;
+        let end: MissionTime = end.parse()?;
+
+        Ok(Self {
+            end: end.after(&start),
+            start,
+        })
+    }
+}
+
+impl fmt::Display for TimeWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} - {}", self.start.text(), self.end.text())
+    }
+}
@@ -0,0 +1,265 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    diagnostic::Diagnostics, to_option, Altitude, Dictionary, InvalidValue, Label, Layout, Section,
+    SectionKind,
+};
+
+const HPA_PER_INHG: f64 = 33.8639;
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct Weather {
+    pub winds: Vec<WindLayer>,
+    pub pressure: Option<Pressure>,
+    /// Nautical miles.
+    pub visibility: Option<f64>,
+    pub cloud_base: Option<Altitude>,
+    pub cloud_tops: Option<Altitude>,
+    /// Lowest altitude contrails form at.
+    pub contrails: Option<Altitude>,
+    /// METAR-style one-liner, `27008KT 10NM BASE045 TOPS090 14C A2992 Q1013`.
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WindLayer {
+    pub altitude: Altitude,
+    /// Degrees the wind blows from, `None` when variable.
+    pub direction: Option<u16>,
+    /// Knots.
+    pub speed: u16,
+    /// Degrees Celsius.
+    pub temperature: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Pressure {
+    pub inhg: f64,
+    pub hpa: f64,
+}
+
+impl Weather {
+    /// The wind layer closest to the ground.
+    pub fn surface(&self) -> Option<&WindLayer> {
+        self.winds.iter().min_by_key(|w| w.altitude.feet())
+    }
+
+    pub(crate) fn from_section(
+        section: &Section,
+        layout: &Layout,
+        dictionary: &Dictionary,
+        diagnostics: &mut Diagnostics,
+    ) -> Self {
+        let mut weather = Self::default();
+
+        let lines = section
+            .lines()
+            .skip(layout.text_start)
+            .map(|(n, l)| (n, l.trim()))
+            .filter(|(_, l)| !l.is_empty());
+
+        for (line, text) in lines {
+            let values: Vec<&str> = text.split('\t').map(str::trim).collect();
+            let mut warn = |e: InvalidValue| {
+                diagnostics.warning(SectionKind::Weather, Some(line), e.to_string())
+            };
+
+            let label = ROW_LABELS
+                .into_iter()
+                .find(|&l| dictionary.is(l, values[0]));
+
+            match (label, &values[..]) {
+                (Some(Label::Altitude), _) => {}
+                (Some(Label::Pressure), [_, pressure, ..]) => {
+                    weather.pressure = Pressure::parse(pressure).map_err(&mut warn).ok();
+                }
+                (Some(Label::Visibility), [_, visibility, ..]) => {
+                    weather.visibility = parse_visibility(visibility).map_err(&mut warn).ok();
+                }
+                (Some(Label::Clouds), [_, clouds @ ..]) => {
+                    for cloud in clouds {
+                        let (field, value) =
+                            if let Some(base) = dictionary.strip_prefix(Label::Base, cloud) {
+                                (&mut weather.cloud_base, base)
+                            } else if let Some(tops) = dictionary.strip_prefix(Label::Tops, cloud) {
+                                (&mut weather.cloud_tops, tops)
+                            } else {
+                                continue;
+                            };
+                        *field = parse_altitude(value, dictionary)
+                            .map_err(&mut warn)
+                            .ok()
+                            .flatten();
+                    }
+                }
+                (Some(Label::Contrails), [_, contrails, ..]) => {
+                    weather.contrails = parse_altitude(contrails, dictionary)
+                        .map_err(&mut warn)
+                        .ok()
+                        .flatten();
+                }
+                (None, [altitude, wind, rest @ ..]) if !altitude.ends_with(':') => {
+                    match WindLayer::parse(altitude, wind, rest.first().copied(), dictionary) {
+                        Ok(layer) => weather.winds.push(layer),
+                        Err(e) => warn(e),
+                    }
+                }
+                _ => diagnostics.warning(
+                    SectionKind::Weather,
+                    Some(line),
+                    format!("unknown weather row `{}`", text),
+                ),
+            }
+        }
+
+        weather.summary = weather.metar();
+        weather
+    }
+
+    fn metar(&self) -> Option<String> {
+        let surface = self.surface();
+        if surface.is_none() && self.pressure.is_none() {
+            return None;
+        }
+
+        let mut parts = vec![];
+        if let Some(wind) = surface {
+            match wind.direction {
+                Some(direction) => parts.push(format!("{:03}{:02}KT", direction, wind.speed)),
+                None if wind.speed == 0 => parts.push("00000KT".to_string()),
+                None => parts.push(format!("VRB{:02}KT", wind.speed)),
+            }
+        }
+        if let Some(visibility) = self.visibility {
+            parts.push(format!("{}NM", visibility));
+        }
+        if let Some(base) = &self.cloud_base {
+            parts.push(format!("BASE{:03}", base.feet() / 100));
+        }
+        if let Some(tops) = &self.cloud_tops {
+            parts.push(format!("TOPS{:03}", tops.feet() / 100));
+        }
+        if let Some(temperature) = surface.and_then(|w| w.temperature) {
+            parts.push(format!("{}C", temperature));
+        }
+        if let Some(pressure) = self.pressure {
+            parts.push(format!("A{:04.0}", pressure.inhg * 100.0));
+            parts.push(format!("Q{:.0}", pressure.hpa));
+        }
+
+        Some(parts.join(" "))
+    }
+}
+
+/// Labels that start a row other than a wind layer.
+const ROW_LABELS: [Label; 5] = [
+    Label::Altitude,
+    Label::Pressure,
+    Label::Visibility,
+    Label::Clouds,
+    Label::Contrails,
+];
+
+impl WindLayer {
+    /// `Surface  270@08kts  14C`
+    fn parse(
+        altitude: &str,
+        wind: &str,
+        temperature: Option<&str>,
+        dictionary: &Dictionary,
+    ) -> Result<Self, InvalidValue> {
+        let altitude = match dictionary.is(Label::Surface, altitude) {
+            true => Altitude::ground(),
+            false => altitude.parse()?,
+        };
+
+        let invalid = || InvalidValue::new("wind", wind);
+        let (direction, speed) = if dictionary.is(Label::Calm, wind) {
+            (None, 0)
+        } else {
+            let (direction, speed) = wind.split_once('@').ok_or_else(invalid)?;
+            let direction = match dictionary.is(Label::Variable, direction) {
+                true => None,
+                false => Some(direction.parse().map_err(|_| invalid())?),
+            };
+            let speed = dictionary
+                .strip_suffix(Label::Knots, speed)
+                .unwrap_or(speed)
+                .parse()
+                .map_err(|_| invalid())?;
+            (direction, speed)
+        };
+
+        let temperature = match temperature.and_then(to_option) {
+            Some(t) => Some(
+                t.trim_end_matches('C')
+                    .parse()
+                    .map_err(|_| InvalidValue::new("temperature", t))?,
+            ),
+            None => None,
+        };
+
+        Ok(Self {
+            altitude,
+            direction,
+            speed,
+            temperature,
+        })
+    }
+}
+
+impl Pressure {
+    /// `29.92 inHg / 1013 hPa`, either half on its own works too.
+    fn parse(text: &str) -> Result<Self, InvalidValue> {
+        let invalid = || InvalidValue::new("pressure", text);
+
+        let mut inhg = None;
+        let mut hpa = None;
+        for part in text.split('/').map(str::trim) {
+            if let Some(value) = part.strip_suffix("inHg") {
+                inhg = Some(value.trim().parse::<f64>().map_err(|_| invalid())?);
+            } else if let Some(value) = part.strip_suffix("hPa") {
+                hpa = Some(value.trim().parse::<f64>().map_err(|_| invalid())?);
+            } else {
+                return Err(invalid());
+            }
+        }
+
+        match (inhg, hpa) {
+            (Some(inhg), Some(hpa)) => Ok(Self { inhg, hpa }),
+            (Some(inhg), None) => Ok(Self {
+                inhg,
+                hpa: (inhg * HPA_PER_INHG).round(),
+            }),
+            (None, Some(hpa)) => Ok(Self {
+                inhg: (hpa / HPA_PER_INHG * 100.0).round() / 100.0,
+                hpa,
+            }),
+            (None, None) => Err(invalid()),
+        }
+    }
+}
+
+/// `10 nm` or `8 km`, in nautical miles.
+fn parse_visibility(text: &str) -> Result<f64, InvalidValue> {
+    let invalid = || InvalidValue::new("visibility", text);
+
+    if let Some(km) = text.strip_suffix("km") {
+        let km: f64 = km.trim().parse().map_err(|_| invalid())?;
+        return Ok((km / 1.852 * 10.0).round() / 10.0);
+    }
+
+    text.trim_end_matches("nm")
+        .trim()
+        .parse()
+        .map_err(|_| invalid())
+}
+
+/// Like [`Altitude`], but `None`/`Clear` mean there is nothing to report.
+fn parse_altitude(text: &str, dictionary: &Dictionary) -> Result<Option<Altitude>, InvalidValue> {
+    match to_option(text) {
+        None => Ok(None),
+        Some(text) if dictionary.is(Label::Clear, text) => Ok(None),
+        Some(text) => text.parse().map(Some),
+    }
+}
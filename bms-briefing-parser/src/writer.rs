@@ -0,0 +1,480 @@
+use std::fmt::Display;
+
+use crate::{
+    Altitude, AltitudeKind, Band, Briefing, CodeChange, Comm, Dictionary, Iff, IffCodes, IffMode,
+    Label, MissionTime, Ordnance, Overview, PackageElement, PilotRoster, SectionKind, Steerpoint,
+    Support, ThreatAnalysis, Weather,
+};
+
+impl Briefing<'_> {
+    /// Writes the briefing back out the way an English BMS 4.35 prints it, so
+    /// that parsing the result gives the same briefing.
+    ///
+    /// Values the parser derives, such as the threat details or the weather
+    /// summary, aren't written. The threat analysis is written from its text.
+    pub fn to_bms_text(&self) -> String {
+        self.to_bms_text_with(Dictionary::english())
+    }
+
+    /// Like [`Briefing::to_bms_text`], with the headers and labels of `dictionary`.
+    ///
+    /// The layout is always the one of BMS 4.35, column titles BMS doesn't
+    /// translate stay in English.
+    pub fn to_bms_text_with(&self, dictionary: &Dictionary) -> String {
+        let mut writer = Writer {
+            dictionary,
+            out: String::new(),
+        };
+
+        writer.overview(&self.overview);
+        writer.paragraphs(SectionKind::Situation, &self.sitrep.text);
+        writer.roster(&self.roster);
+        writer.elements(&self.elements);
+        writer.threat_analysis(&self.threat_analysis);
+        writer.steerpoints(&self.steerpoints);
+        writer.comms(&self.comms);
+        writer.iff(&self.iff);
+        writer.ordnance(&self.ordnance);
+        writer.weather(&self.weather);
+        writer.support(&self.support);
+        writer.paragraphs(SectionKind::RulesOfEngagement, &self.roe.text);
+        writer.paragraphs(SectionKind::EmergencyProcedures, &self.emergency.text);
+        writer.out.push_str("End of Briefing\n");
+
+        writer.out
+    }
+}
+
+struct Writer<'d> {
+    dictionary: &'d Dictionary<'d>,
+    out: String,
+}
+
+impl<'d> Writer<'d> {
+    fn header(&mut self, kind: SectionKind) {
+        self.out.push_str(self.dictionary.header(kind));
+        self.out.push_str(":\n");
+        self.blank();
+    }
+
+    fn label(&self, label: Label) -> &'d str {
+        self.dictionary.label(label)
+    }
+
+    /// The label followed by a colon, as the first cell of a row.
+    fn field(&self, label: Label) -> String {
+        format!("{}:", self.label(label))
+    }
+
+    fn blank(&mut self) {
+        self.out.push_str("\t\n");
+    }
+
+    /// A tab indented line of tab separated cells.
+    fn row<T: Display>(&mut self, cells: impl IntoIterator<Item = T>) {
+        for cell in cells {
+            self.out.push('\t');
+            self.out.push_str(&cell.to_string());
+        }
+        self.out.push('\n');
+    }
+
+    fn paragraphs(&mut self, kind: SectionKind, text: &str) {
+        self.header(kind);
+        for line in text.lines() {
+            self.row([line]);
+        }
+    }
+
+    fn overview(&mut self, overview: &Overview) {
+        self.header(SectionKind::Overview);
+        self.row([with_parenthesis(
+            overview.callsign.to_string(),
+            &overview.mission_type,
+        )]);
+        self.blank();
+        self.row([with_parenthesis(
+            format!("Package: {}", overview.package_id),
+            &overview.package_description,
+        )]);
+        self.blank();
+
+        let fields = [
+            (Label::PackageMission, &overview.package_mission),
+            (Label::TargetArea, &overview.target_area),
+            (Label::TimeOnTarget, &overview.time_on_target),
+            (Label::Sunrise, &overview.sunrise),
+            (Label::Sunset, &overview.sunset),
+        ];
+        for (label, value) in fields {
+            self.row([self.field(label), value.to_string()]);
+        }
+    }
+
+    fn roster(&mut self, roster: &[PilotRoster]) {
+        self.header(SectionKind::PilotRoster);
+        self.row(["Callsign", "Lead", "Wing", "Element", "Four"]);
+        self.blank();
+        for pilots in roster {
+            self.row([
+                &pilots.callsign,
+                &pilots.lead,
+                &pilots.wing,
+                &pilots.element,
+                &pilots.four,
+            ]);
+        }
+    }
+
+    fn elements(&mut self, elements: &[PackageElement]) {
+        self.header(SectionKind::PackageElements);
+        self.row(["Callsign", "Flight #", "Role", "Aircraft", "Task"]);
+        self.blank();
+        for element in elements {
+            let mut timing = vec![element.task.as_deref().map(str::to_string)];
+            timing.extend(
+                element
+                    .tot
+                    .as_ref()
+                    .map(|t| Some(format!("{} {}", self.label(Label::Tot), t.text()))),
+            );
+            timing.extend(element.window.as_ref().map(|w| Some(w.to_string())));
+
+            let columns = [
+                vec![
+                    element.flight_number.map(|n| n.to_string()),
+                    element
+                        .size
+                        .map(|s| format!("{} {}", s, self.label(Label::Ship))),
+                ],
+                vec![
+                    element.role.as_deref().map(str::to_string),
+                    element.priority.as_deref().map(str::to_string),
+                ],
+                vec![
+                    element.aircraft.as_deref().map(str::to_string),
+                    element.squadron.as_deref().map(str::to_string),
+                ],
+                timing,
+                element.notes.iter().map(|n| Some(n.to_string())).collect(),
+            ];
+
+            // Flights take as many lines as their longest column, the callsign
+            // only goes on the first.
+            let lines = columns.iter().map(Vec::len).max().unwrap_or(0);
+            for line in 0..lines {
+                let callsign = if line == 0 { &*element.callsign } else { "" };
+                let mut cells: Vec<String> = [callsign.to_string()]
+                    .into_iter()
+                    .chain(
+                        columns
+                            .iter()
+                            .map(|c| c.get(line).cloned().flatten().unwrap_or_default()),
+                    )
+                    .collect();
+                while cells.len() > 1 && cells.last().is_some_and(String::is_empty) {
+                    cells.pop();
+                }
+                if line == 0 || cells.len() > 1 {
+                    self.row(cells);
+                }
+            }
+        }
+    }
+
+    fn threat_analysis(&mut self, threat_analysis: &ThreatAnalysis) {
+        // Only briefings that had the section were looked up in a database.
+        if threat_analysis.database.is_empty() {
+            return;
+        }
+
+        self.header(SectionKind::ThreatAnalysis);
+        for line in threat_analysis.text.lines().map(str::trim) {
+            self.row([line]);
+        }
+    }
+
+    fn steerpoints(&mut self, steerpoints: &[Steerpoint]) {
+        self.header(SectionKind::Steerpoints);
+        self.row([
+            "#",
+            "Description",
+            "Time",
+            "Dist",
+            "Hdg",
+            "CAS",
+            "Alt",
+            "Action",
+            "Form",
+            "Comments",
+        ]);
+        self.blank();
+        for steerpoint in steerpoints {
+            self.row([
+                steerpoint.steerpoint.to_string(),
+                or_dashes(steerpoint.description.as_deref()),
+                or_dashes(steerpoint.time.as_ref().map(MissionTime::text)),
+                // Debug keeps the decimal point, `0.0` rather than `0`.
+                or_dashes(steerpoint.distance.map(|d| format!("{:?}", d))),
+                or_dashes(steerpoint.heading),
+                or_dashes(steerpoint.cas),
+                or_dashes(steerpoint.altitude.as_ref().map(Altitude::text)),
+                or_dashes(steerpoint.action.as_deref()),
+                or_dashes(steerpoint.form.as_deref()),
+                or_dashes(steerpoint.comments.as_deref()),
+            ]);
+        }
+    }
+
+    fn comms(&mut self, comms: &[Comm]) {
+        self.header(SectionKind::CommLadder);
+        self.row(["Agency", "Callsign", "UHF", "VHF", "Notes"]);
+        self.blank();
+        for comm in comms {
+            self.row([
+                format!("{}:", comm.agency),
+                or_dashes(comm.raw_callsign.as_ref().or(comm.callsign.as_ref())),
+                or_dashes(comm.uhf),
+                or_dashes(comm.vhf),
+                or_dashes(comm.notes.as_deref()),
+            ]);
+        }
+    }
+
+    fn iff(&mut self, iff: &Iff) {
+        let initial = &iff.initial;
+
+        self.header(SectionKind::Iff);
+        self.row([self.label(Label::InitialSettings)]);
+
+        let modes: Vec<String> = initial
+            .modes_active
+            .iter()
+            .map(|m| m.to_string().trim_start_matches('M').to_string())
+            .collect();
+        let mut cells = vec![
+            self.field(Label::Initial),
+            format!("{} {}", self.field(Label::ModesActive), modes.join(",")),
+            self.field(Label::Codes),
+        ];
+        cells.extend(codes(&initial.codes).map(|(mode, code)| format!("{}: {}", mode, code)));
+        self.row(cells);
+
+        if !initial.m4_validity.is_empty() {
+            let until_label = self.label(Label::Until);
+            let cells = initial.m4_validity.iter().map(|v| {
+                // BMS ends the last period at 24:00:00 rather than midnight.
+                let until = match v.until == MissionTime::from_seconds(24 * 60 * 60) {
+                    true => "24:00:00".into(),
+                    false => v.until.text(),
+                };
+                format!("{:?} {} {}", v.key, until_label, until)
+            });
+            self.row([self.field(Label::M4Validity)].into_iter().chain(cells));
+        }
+
+        let policy: Vec<String> = IffMode::ALL
+            .into_iter()
+            .filter_map(|mode| {
+                let on = initial.policy.get(mode)?;
+                let label = if on { Label::On } else { Label::Off };
+                Some(format!("{}: {}", mode, self.label(label)))
+            })
+            .collect();
+        self.row([self.field(Label::Policy)].into_iter().chain(policy));
+
+        if let Some(code_change) = initial.code_change {
+            let label = match code_change {
+                CodeChange::Automatic => Label::Automatic,
+                CodeChange::Manual => Label::Manual,
+            };
+            self.row([self.field(Label::CodeChange), self.label(label).to_string()]);
+        }
+
+        self.blank();
+        self.row([self.label(Label::TimeEvents)]);
+        self.row(["", "M1", "M2", "M3", "M4"]);
+        for event in &iff.time_events {
+            self.row([
+                event.time.text().into_owned(),
+                or_dashes(event.codes.m1),
+                or_dashes(event.codes.m2),
+                or_dashes(event.codes.m3),
+                or_dashes(event.codes.m4.map(|k| format!("{:?}", k))),
+            ]);
+        }
+
+        let steerpoint = self.label(Label::Steerpoint);
+        let mut position_events: Vec<String> = iff
+            .position_events
+            .iter()
+            .flat_map(|event| {
+                codes(&event.codes).map(move |(mode, code)| {
+                    format!("{} {}: {} {}", steerpoint, event.steerpoint, mode, code)
+                })
+            })
+            .collect();
+        if position_events.is_empty() {
+            position_events.push("None".to_string());
+        }
+        self.row(
+            [self.field(Label::PositionEvents)]
+                .into_iter()
+                .chain(position_events),
+        );
+    }
+
+    fn ordnance(&mut self, ordnance: &Ordnance) {
+        self.header(SectionKind::Ordnance);
+        for flight in &ordnance.flights {
+            self.blank();
+
+            let aircraft = flight
+                .aircraft
+                .iter()
+                .map(|a| format!("--{}--", a.callsign));
+            self.row([flight.callsign.to_string()].into_iter().chain(aircraft));
+
+            let stations = flight.aircraft.iter().map(|a| a.stations.len()).max();
+            for station in 0..stations.unwrap_or(0) {
+                self.row(
+                    flight
+                        .aircraft
+                        .iter()
+                        .map(|a| match a.stations.get(station) {
+                            Some(store) => format!("{} {}", store.quantity, store.name),
+                            None => String::new(),
+                        }),
+                );
+            }
+        }
+    }
+
+    fn weather(&mut self, weather: &Weather) {
+        self.header(SectionKind::Weather);
+        self.row([self.label(Label::Altitude), "Wind", "Temperature"]);
+        for layer in &weather.winds {
+            let altitude = match layer.altitude.is_ground() {
+                true => self.label(Label::Surface).to_string(),
+                false => layer.altitude.text().into_owned(),
+            };
+            let knots = self.label(Label::Knots);
+            let wind = match (layer.direction, layer.speed) {
+                (Some(direction), speed) => format!("{:03}@{:02}{}", direction, speed, knots),
+                (None, 0) => self.label(Label::Calm).to_string(),
+                (None, speed) => {
+                    format!("{}@{:02}{}", self.label(Label::Variable), speed, knots)
+                }
+            };
+            let temperature = or_dashes(layer.temperature.map(|t| format!("{}C", t)));
+            self.row([altitude, wind, temperature]);
+        }
+
+        if let Some(pressure) = weather.pressure {
+            self.row([
+                self.field(Label::Pressure),
+                format!("{} inHg / {} hPa", pressure.inhg, pressure.hpa),
+            ]);
+        }
+        if let Some(visibility) = weather.visibility {
+            self.row([self.field(Label::Visibility), format!("{} nm", visibility)]);
+        }
+        if weather.cloud_base.is_some() || weather.cloud_tops.is_some() {
+            let base = weather
+                .cloud_base
+                .as_ref()
+                .map(|b| format!("{} {}", self.label(Label::Base), feet(b)));
+            let tops = weather
+                .cloud_tops
+                .as_ref()
+                .map(|t| format!("{} {}", self.label(Label::Tops), feet(t)));
+            self.row(
+                [self.field(Label::Clouds)]
+                    .into_iter()
+                    .chain(base)
+                    .chain(tops),
+            );
+        }
+        if let Some(contrails) = &weather.contrails {
+            self.row([self.field(Label::Contrails), feet(contrails)]);
+        }
+    }
+
+    fn support(&mut self, support: &Support) {
+        self.header(SectionKind::Support);
+        self.row(["Callsign", "Type", "Description"]);
+        self.blank();
+        for asset in &support.assets {
+            let mut description = vec![asset.aircraft.as_deref().unwrap_or("").to_string()];
+            description.extend(asset.frequency.map(|f| {
+                let band = match f.band() {
+                    Band::Uhf => Label::Uhf,
+                    Band::Vhf => Label::Vhf,
+                };
+                format!("{} {}", self.label(band), f)
+            }));
+            description.extend(
+                asset
+                    .tacan
+                    .map(|t| format!("{} {}", self.label(Label::Tacan), t)),
+            );
+            self.row([
+                format!("{}:", asset.callsign),
+                asset.kind.to_string(),
+                description.join(", "),
+            ]);
+
+            let mut details = vec![];
+            details.extend(
+                asset
+                    .track
+                    .as_deref()
+                    .map(|t| format!("{} {}", self.label(Label::Track), t)),
+            );
+            details.extend(
+                asset
+                    .on_station
+                    .as_ref()
+                    .map(|w| format!("{} {}", self.label(Label::OnStation), w)),
+            );
+            details.extend(asset.notes.iter().map(|n| n.to_string()));
+            if !details.is_empty() {
+                self.row([details.join(", ")]);
+            }
+        }
+    }
+}
+
+/// The codes that are set, paired with their mode.
+fn codes(codes: &IffCodes) -> impl Iterator<Item = (IffMode, String)> {
+    [
+        (IffMode::M1, codes.m1.map(|c| c.to_string())),
+        (IffMode::M2, codes.m2.map(|c| c.to_string())),
+        (IffMode::M3, codes.m3.map(|c| c.to_string())),
+        (IffMode::M4, codes.m4.map(|k| format!("{:?}", k))),
+    ]
+    .into_iter()
+    .filter_map(|(mode, code)| Some((mode, code?)))
+}
+
+/// Weather altitudes carry their unit, `4500ft`.
+fn feet(altitude: &Altitude) -> String {
+    match (altitude.source(), altitude.kind) {
+        (Some(text), _) => text.to_string(),
+        (None, AltitudeKind::Msl) => format!("{}ft", altitude.value),
+        (None, _) => altitude.to_string(),
+    }
+}
+
+/// `Viper1 (Strike)`, or just `Viper1` when there's nothing to put in parentheses.
+fn with_parenthesis(text: String, parenthesis: &str) -> String {
+    match parenthesis.is_empty() {
+        true => text,
+        false => format!("{} ({})", text, parenthesis),
+    }
+}
+
+/// BMS prints `--` for empty cells.
+fn or_dashes(value: Option<impl Display>) -> String {
+    value.map_or_else(|| "--".to_string(), |v| v.to_string())
+}
@@ -0,0 +1,118 @@
+//! Translated briefings parse into the same model as English ones.
+
+use std::{fs, path::PathBuf};
+
+use bms_briefing_parser::{Briefing, Dictionary, Label, LocationReference, SectionKind};
+
+fn fixture(name: &str) -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/synthetic")
+        .join(name);
+    fs::read_to_string(path).unwrap()
+}
+
+#[test]
+fn english_covers_every_label() {
+    let english = Dictionary::english();
+    for label in Label::ALL {
+        assert!(!english.label(label).is_empty(), "{}", label.key());
+    }
+    for kind in SectionKind::ALL {
+        assert_eq!(english.header(kind), kind.header());
+    }
+}
+
+#[test]
+fn german_briefings_match_english() {
+    let english = fixture("kto-campaign-strike-4ship.txt");
+    let german = fixture("kto-campaign-strike-4ship-de.txt");
+
+    assert_eq!(Dictionary::detect(&english).language(), "English");
+    assert_eq!(Dictionary::detect(&german).language(), "Deutsch");
+
+    let english = serde_json::to_value(Briefing::parse_lenient(&english)).unwrap();
+    let german = serde_json::to_value(Briefing::parse_lenient(&german)).unwrap();
+    assert_eq!(english, german);
+}
+
+#[test]
+fn german_weather_labels() {
+    let english = "Weather:\n\t\n\tAltitude\tWind\tTemperature\n\
+        \tSurface\t220@12kts\t24C\n\
+        \tPressure:\t29.92 inHg / 1013 hPa\n\
+        \tVisibility:\t8 km\n\
+        \tClouds:\tBase 6000ft\tTops 7500ft\n\
+        \tContrails:\tFL260\n";
+    let german = "Wetter:\n\t\n\tHöhe\tWind\tTemperatur\n\
+        \tBoden\t220@12kts\t24C\n\
+        \tLuftdruck:\t29.92 inHg / 1013 hPa\n\
+        \tSicht:\t8 km\n\
+        \tWolken:\tUntergrenze 6000ft\tObergrenze 7500ft\n\
+        \tKondensstreifen:\tFL260\n";
+
+    let english = Briefing::parse_lenient_with(english, Dictionary::english()).briefing;
+    let german = Briefing::parse_lenient_with(german, Dictionary::german()).briefing;
+    assert!(german.weather.surface().unwrap().altitude.is_ground());
+    assert_eq!(
+        german.weather.cloud_tops.as_ref().map(|a| a.feet()),
+        Some(7_500)
+    );
+    assert_eq!(
+        serde_json::to_value(&english.weather).unwrap(),
+        serde_json::to_value(&german.weather).unwrap()
+    );
+}
+
+#[test]
+fn user_tables_fall_back_to_english() {
+    let table = "language\tFrançais\n\
+        header\tsituation\tSituation tactique\n\
+        header\tweather\tMétéo\n\
+        label\tpressure\tPression\n";
+    let dictionary = Dictionary::parse(table).unwrap();
+    assert_eq!(dictionary.header(SectionKind::Steerpoints), "Steerpoints");
+    assert_eq!(dictionary.label(Label::Visibility), "Visibility");
+
+    let text = "Situation tactique:\n\
+        \t\n\
+        \tRAS.\n\
+        Météo:\n\
+        \t\n\
+        \tPression:\t1013 hPa\n\
+        \tVisibility:\t10 nm\n";
+    let briefing = Briefing::parse_lenient_with(text, &dictionary).briefing;
+
+    assert_eq!(briefing.sitrep.text, "RAS.");
+    assert_eq!(briefing.weather.pressure.unwrap().hpa, 1013.0);
+    assert_eq!(briefing.weather.visibility, Some(10.0));
+}
+
+#[test]
+fn german_threat_locations() {
+    let text = "Bedrohungsanalyse:\n\t\n\tHawk Stellung 12nm nördlich von Steuerpunkt 5.\n";
+    let briefing = Briefing::parse_lenient_with(text, Dictionary::german()).briefing;
+
+    let threat = &briefing.threat_analysis.threats[0];
+    let location = threat.location.as_ref().unwrap();
+    assert_eq!(threat.system, "Hawk");
+    assert_eq!(location.distance, Some(12.0));
+    assert_eq!(location.direction.as_deref(), Some("nördlich"));
+    assert_eq!(location.reference, Some(LocationReference::Steerpoint(5)));
+}
+
+#[test]
+fn labels_have_other_spellings() {
+    let english = Dictionary::english();
+    assert_eq!(english.label(Label::Surface), "Surface");
+
+    let text = "Weather:\n\t\n\tAltitude\tWind\tTemperature\n\tSFC\tVRB@05kt\t14C\n";
+    let layer = &Briefing::parse_lenient(text).briefing.weather.winds[0];
+    assert!(layer.altitude.is_ground());
+    assert_eq!((layer.direction, layer.speed), (None, 5));
+}
+
+#[test]
+fn unknown_entries_are_rejected() {
+    assert!(Dictionary::parse("language\tX\nlabel\tnope\tNope\n").is_err());
+    assert!(Dictionary::parse("header\tweather\tWetter\n").is_err());
+}
@@ -0,0 +1,116 @@
+//! What changed between a briefing and the one BMS prints after the mission
+//! was replanned.
+
+use std::{fs, path::PathBuf};
+
+use bms_briefing_parser::{Briefing, Change, MissionTime};
+
+fn fixture(name: &str) -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/synthetic")
+        .join(name);
+    fs::read_to_string(path).unwrap()
+}
+
+/// The strike fixture after pushing steerpoint 3 back, moving the tower to
+/// another frequency, swapping Viper11's bombs and a new SAM site.
+fn replanned(text: &str) -> String {
+    let replaced = [
+        (
+            "\t3\tPush\t07:55:00\t60.1\t330\t",
+            "\t3\tPush\t07:57:30\t62.0\t330\t",
+        ),
+        ("\t292.300 (1)\t", "\t292.500 (1)\t"),
+        (
+            "\t2 GBU-31\t2 GBU-31\t2 GBU-31\t2 GBU-31\n",
+            "\t4 GBU-12\t2 GBU-31\t2 GBU-31\t2 GBU-31\n",
+        ),
+        (
+            "\tZSU-23-4 reported in the target area.\n",
+            "\tZSU-23-4 reported in the target area.\n\tSA-6 battery 20nm west of steerpoint 3.\n",
+        ),
+    ];
+
+    let mut text = text.to_string();
+    for (from, to) in replaced {
+        assert!(text.contains(from), "fixture has no `{}`", from);
+        text = text.replacen(from, to, 1);
+    }
+    text
+}
+
+#[test]
+fn same_briefing_has_no_changes() {
+    let text = fixture("kto-campaign-strike-4ship.txt");
+    let briefing = Briefing::parse_lenient(&text).briefing;
+
+    assert_eq!(briefing.diff(&briefing), vec![]);
+}
+
+#[test]
+fn replanned_mission() {
+    let text = fixture("kto-campaign-strike-4ship.txt");
+    let replanned = replanned(&text);
+    let before = Briefing::parse_lenient(&text).briefing;
+    let after = Briefing::parse_lenient(&replanned).briefing;
+
+    let changes = before.diff(&after);
+    assert_eq!(changes.len(), 5, "{:#?}", changes);
+
+    assert!(matches!(
+        &changes[0],
+        Change::SteerpointMoved { steerpoint: 3, distance: (Some(from), Some(to)), .. }
+            if *from == 60.1 && *to == 62.0
+    ));
+    assert!(matches!(
+        &changes[1],
+        Change::SteerpointRetimed { steerpoint: 3, from: Some(from), to: Some(to) }
+            if Some(from) == MissionTime::from_hms(7, 55, 0).as_ref()
+                && Some(to) == MissionTime::from_hms(7, 57, 30).as_ref()
+    ));
+    assert!(matches!(
+        &changes[2],
+        Change::FrequencyChanged { agency, to: Some(to), .. }
+            if agency == "Tower" && to.khz() == Some(292_500)
+    ));
+    match &changes[3] {
+        Change::LoadoutChanged {
+            flight,
+            aircraft,
+            to,
+            ..
+        } => {
+            assert_eq!((flight.as_str(), aircraft.as_str()), ("Viper1", "Viper11"));
+            assert_eq!(to[1].name, "GBU-12");
+            assert_eq!(to[1].quantity, 4);
+        }
+        change => panic!("expected a loadout change, got {:?}", change),
+    }
+    assert!(matches!(&changes[4], Change::ThreatAdded { system, .. } if system == "SA-6"));
+
+    // The other way around everything is undone.
+    let changes = after.diff(&before);
+    assert!(matches!(&changes[4], Change::ThreatRemoved { system, .. } if system == "SA-6"));
+}
+
+#[test]
+fn added_and_removed_steerpoints() {
+    let text = fixture("kto-campaign-strike-4ship.txt");
+    let shorter = text.replace(
+        "\t5\tLand\t08:50:00\t120.4\t160\t300\tGND\tLand\t--\t--\n",
+        "",
+    );
+    assert_ne!(text, shorter);
+
+    let before = Briefing::parse_lenient(&text).briefing;
+    let after = Briefing::parse_lenient(&shorter).briefing;
+
+    assert_eq!(
+        before.diff(&after),
+        vec![Change::SteerpointRemoved { steerpoint: 5 }]
+    );
+    assert_eq!(
+        after.diff(&before),
+        vec![Change::SteerpointAdded { steerpoint: 5 }]
+    );
+}
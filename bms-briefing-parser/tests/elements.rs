@@ -0,0 +1,61 @@
+use bms_briefing_parser::{Briefing, SectionKind};
+
+const ELEMENTS: &str = "Mission Overview:
+\t
+\tViper2 (Interdiction)
+\t
+\tPackage: 7000 (Interdiction Kaesong)
+Package Elements:
+\t
+\tCallsign\tFlight #\tRole\tAircraft\tTask
+\t
+\tViper2\t7001\tInterdiction\tF-16CM-52\tBAI
+\t\t2 ship\tPrimary\t80th FS\tTOT 08:30:00
+\tHog4\t70O3\tCAS\tA-10C\tCAS
+\t\t2 ship\tSecondary\t25th FS\t08:00:00 - 09:00:00
+\t\t\t\t\tPlaytime 20 min
+";
+
+#[test]
+fn elements_span_two_rows() {
+    let elements = Briefing::parse_lenient(ELEMENTS).briefing.elements;
+    let viper = &elements[0];
+
+    assert!(viper.is_primary);
+    assert_eq!(viper.callsign, "Viper2");
+    assert_eq!(viper.flight_number, Some(7001));
+    assert_eq!(viper.size, Some(2));
+    assert_eq!(viper.role.as_deref(), Some("Interdiction"));
+    assert_eq!(viper.priority.as_deref(), Some("Primary"));
+    assert_eq!(viper.aircraft.as_deref(), Some("F-16CM-52"));
+    assert_eq!(viper.squadron.as_deref(), Some("80th FS"));
+    assert_eq!(viper.task.as_deref(), Some("BAI"));
+    assert_eq!(viper.tot.as_ref().map(|t| t.as_seconds()), Some(30_600));
+    assert_eq!(viper.window, None);
+}
+
+#[test]
+fn windows_and_notes() {
+    let parsed = Briefing::parse_lenient(ELEMENTS);
+    let hog = &parsed.briefing.elements[1];
+
+    assert!(!hog.is_primary);
+    assert_eq!(hog.tot, None);
+    let window = hog.window.as_ref().unwrap();
+    assert_eq!(
+        (window.start.as_seconds(), window.end.as_seconds()),
+        (8 * 3600, 9 * 3600)
+    );
+    assert_eq!(hog.notes, ["Playtime 20 min"]);
+
+    // The rest of the element survives an invalid flight number.
+    assert_eq!(hog.flight_number, None);
+    assert_eq!(hog.aircraft.as_deref(), Some("A-10C"));
+    let warnings: Vec<_> = parsed
+        .diagnostics
+        .iter()
+        .filter(|d| d.section == SectionKind::PackageElements)
+        .map(|d| (d.line, d.message.as_str()))
+        .collect();
+    assert_eq!(warnings, [(Some(12), "invalid flight number `70O3`")]);
+}
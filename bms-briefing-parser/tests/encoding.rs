@@ -0,0 +1,105 @@
+//! Briefings read from disk: encodings BMS and other tools write, and the
+//! characters they carry that an ASCII-only reader would lose.
+
+use bms_briefing_parser::{decode, Briefing, Encoding, Severity};
+
+const BRIEFING: &str = "Mission Overview:\r\n\
+    \t\r\n\
+    \tViper1 (Strike)\r\n\
+    Pilot Roster:\r\n\
+    \t\r\n\
+    \tCallsign\tLead\tWing\tElement\tFour\r\n\
+    \t\r\n\
+    \tViper1\tJérôme\tSøren\tŁukasz\tAI\r\n\
+    Situation:\r\n\
+    \t\r\n\
+    \tWinds 270° at 8 knots.\r\n";
+
+fn utf16(text: &str, little_endian: bool, bom: bool) -> Vec<u8> {
+    let bom = bom.then_some('\u{feff}');
+    bom.into_iter()
+        .chain(text.chars())
+        .collect::<String>()
+        .encode_utf16()
+        .flat_map(|unit| match little_endian {
+            true => unit.to_le_bytes(),
+            false => unit.to_be_bytes(),
+        })
+        .collect()
+}
+
+fn check(bytes: &[u8], encoding: Encoding) {
+    let decoded = decode(bytes, None);
+    assert_eq!(decoded.encoding, encoding);
+    assert!(!decoded.had_errors);
+    assert_eq!(decoded.text, BRIEFING.replace("\r\n", "\n"));
+
+    let briefing = Briefing::parse_bytes(bytes).briefing;
+    let pilots = &briefing.roster[0];
+    assert_eq!(
+        [&pilots.lead, &pilots.wing, &pilots.element],
+        ["Jérôme", "Søren", "Łukasz"]
+    );
+    assert_eq!(briefing.sitrep.text, "Winds 270° at 8 knots.");
+}
+
+#[test]
+fn utf8_with_and_without_bom() {
+    check(BRIEFING.as_bytes(), Encoding::Utf8);
+    check(
+        &[b"\xef\xbb\xbf", BRIEFING.as_bytes()].concat(),
+        Encoding::Utf8,
+    );
+}
+
+#[test]
+fn utf16_with_and_without_bom() {
+    check(&utf16(BRIEFING, true, true), Encoding::Utf16Le);
+    check(&utf16(BRIEFING, false, true), Encoding::Utf16Be);
+    check(&utf16(BRIEFING, true, false), Encoding::Utf16Le);
+    check(&utf16(BRIEFING, false, false), Encoding::Utf16Be);
+}
+
+#[test]
+fn windows_1252() {
+    let bytes = b"Situation:\r\n\t\r\n\tJ\xe9r\xf4me reports 12\xb0C.\r\n";
+
+    let decoded = decode(bytes, None);
+    assert_eq!(decoded.encoding, Encoding::Windows1252);
+    assert_eq!(decoded.text, "Situation:\n\t\n\tJérôme reports 12°C.\n");
+
+    // Pure ASCII is the same either way, so it may be read as UTF-8.
+    let ascii = b"Situation:\r\n\t\r\n\tAll clear.\r\n";
+    let forced = decode(ascii, Some(Encoding::Windows1252));
+    assert_eq!(forced.text, decode(ascii, None).text);
+}
+
+#[test]
+fn windows_1252_briefing_from_disk() {
+    let bytes = std::fs::read(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/synthetic/balkans-te-barcap-2ship-cp1252.txt"
+    ))
+    .unwrap();
+    assert!(bytes.windows(2).any(|w| w == b"\r\n"));
+
+    let decoded = decode(&bytes, None);
+    assert_eq!(decoded.encoding, Encoding::Windows1252);
+    assert!(!decoded.had_errors);
+    assert!(!decoded.text.contains('\r'));
+
+    let parsed = Briefing::parse_bytes(&bytes);
+    assert!(parsed
+        .diagnostics
+        .iter()
+        .all(|d| d.severity != Severity::Error));
+    assert_eq!(parsed.briefing.roster[0].lead, "Jérôme");
+    assert_eq!(parsed.briefing.roster[0].wing, "Søren");
+    assert!(parsed.briefing.sitrep.text.contains("Višegrad is 12°C"));
+}
+
+#[test]
+fn line_endings_are_normalized() {
+    let decoded = decode(b"Situation:\r\t\r\n\tA\rB\n", None);
+    assert_eq!(decoded.text, "Situation:\n\t\n\tA\nB\n");
+}
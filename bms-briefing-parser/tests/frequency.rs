@@ -0,0 +1,74 @@
+use bms_briefing_parser::{Band, Briefing, Frequency, SectionKind};
+
+#[test]
+fn frequencies_parse_to_khz() {
+    let frequency = Frequency::parse("283.2 (3)", Band::Uhf).unwrap();
+    assert_eq!(frequency.khz(), Some(283_200));
+    assert_eq!(frequency.preset(), Some(3));
+}
+
+#[test]
+fn huge_frequencies_are_invalid() {
+    for text in ["99999999.5", "4294967.296", "99999999999999999999"] {
+        assert!(Frequency::parse(text, Band::Uhf).is_err(), "{}", text);
+    }
+}
+
+const COMM_LADDER: &str = "Comm Ladder:
+\t
+\tAgency\tCallsign\tUHF\tVHF\tNotes
+\t
+\tPackage:\tHawg1\t250.000 (5)\t--\tPrimary
+\tFlight:\tHawg1\t(6)\t(7)\tPresets only
+\tTower:\tOsan Tower [12]\t292.3\t126.200\tShort frequency
+\tJTAC:\tWarhawk\t125.000 (8)\t--\tUHF out of band
+\tGround:\tOsan Ground\t99999999.5\t--\tToo large
+\tAWACS:\tMagic\t245.000 (21)\t--\tBad preset
+";
+
+#[test]
+fn comm_ladder_frequencies() {
+    let comms = Briefing::parse_lenient(COMM_LADDER).briefing.comms;
+
+    let package = comms[0].uhf.unwrap();
+    assert_eq!((package.khz(), package.preset()), (Some(250_000), Some(5)));
+
+    let flight = &comms[1];
+    assert_eq!(
+        flight.uhf.map(|f| (f.khz(), f.preset())),
+        Some((None, Some(6)))
+    );
+    assert_eq!(
+        flight.vhf.map(|f| (f.khz(), f.preset())),
+        Some((None, Some(7)))
+    );
+
+    let tower = &comms[2];
+    assert_eq!(tower.uhf.and_then(Frequency::khz), Some(292_300));
+    assert_eq!(tower.vhf.and_then(Frequency::khz), Some(126_200));
+}
+
+#[test]
+fn invalid_comm_frequencies_are_left_out_with_a_warning() {
+    let parsed = Briefing::parse_lenient(COMM_LADDER);
+    let comms = &parsed.briefing.comms;
+
+    for comm in &comms[3..] {
+        assert_eq!(comm.uhf, None, "{}", comm.agency);
+    }
+
+    let warnings: Vec<_> = parsed
+        .diagnostics
+        .iter()
+        .filter(|d| d.section == SectionKind::CommLadder)
+        .map(|d| (d.line, d.message.as_str()))
+        .collect();
+    assert_eq!(
+        warnings,
+        [
+            (Some(8), "invalid frequency `125.000 (8)`"),
+            (Some(9), "invalid frequency `99999999.5`"),
+            (Some(10), "invalid frequency `245.000 (21)`"),
+        ]
+    );
+}
@@ -0,0 +1,120 @@
+//! Golden-file tests: every `tests/synthetic/*.txt` briefing is parsed and
+//! compared against the `.json` snapshot next to it.
+//!
+//! The briefings are written by hand in the layout BMS 4.35 prints, none of
+//! them is an export from BMS. The snapshots are the parser's own output, so
+//! they catch changes in how a briefing is read, not whether it's read right.
+//!
+//! Fixtures are read as bytes, the way the server reads `briefing.txt`.
+//!
+//! To add a briefing, drop it in `tests/synthetic/` and run the tests with
+//! `UPDATE_GOLDEN=1` to write its snapshot. Review the generated JSON before
+//! committing it, the same goes for any snapshot that changes.
+
+use std::{env, fs, path::PathBuf};
+
+use bms_briefing_parser::{decode, Briefing};
+
+fn fixtures() -> Vec<PathBuf> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/synthetic");
+
+    let mut fixtures: Vec<PathBuf> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|e| e == "txt"))
+        .collect();
+    fixtures.sort();
+
+    fixtures
+}
+
+/// The fixture's text, decoded as the parser would.
+fn read(fixture: &PathBuf) -> String {
+    decode(&fs::read(fixture).unwrap(), None).text
+}
+
+#[test]
+fn fixtures_match_snapshots() {
+    let update = env::var_os("UPDATE_GOLDEN").is_some();
+
+    let fixtures = fixtures();
+    assert!(!fixtures.is_empty(), "no fixtures found");
+
+    let mut failures = vec![];
+    for fixture in fixtures {
+        let parsed = Briefing::parse_bytes(&fs::read(&fixture).unwrap());
+        let actual = serde_json::to_string_pretty(&parsed).unwrap() + "\n";
+
+        let snapshot = fixture.with_extension("json");
+        if update {
+            fs::write(&snapshot, &actual).unwrap();
+            continue;
+        }
+
+        let Ok(expected) = fs::read_to_string(&snapshot) else {
+            failures.push(format!("{}: missing snapshot", snapshot.display()));
+            continue;
+        };
+
+        if expected != actual {
+            let (line, (expected, actual)) = expected
+                .lines()
+                .chain(["<end of file>"])
+                .zip(actual.lines().chain(["<end of file>"]))
+                .enumerate()
+                .find(|(_, (e, a))| e != a)
+                .unwrap_or_default();
+
+            failures.push(format!(
+                "{}:{}\n  expected: {}\n  actual:   {}",
+                snapshot.display(),
+                line + 1,
+                expected.trim(),
+                actual.trim()
+            ));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "snapshots differ, rerun with UPDATE_GOLDEN=1 if the change is intended:\n{}",
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn owned_briefings_outlive_their_text() {
+    for fixture in fixtures() {
+        let text = read(&fixture);
+        let borrowed = serde_json::to_string(&Briefing::parse_lenient(&text)).unwrap();
+
+        let owned = Briefing::parse_lenient(&text).into_owned();
+        drop(text);
+
+        // Owned briefings can be handed to other threads.
+        let owned = std::thread::spawn(move || serde_json::to_string(&owned).unwrap())
+            .join()
+            .unwrap();
+
+        assert_eq!(borrowed, owned, "{}", fixture.display());
+    }
+}
+
+#[test]
+fn written_briefings_parse_back() {
+    for fixture in fixtures() {
+        let text = read(&fixture);
+        let parsed = Briefing::parse_lenient(&text).briefing;
+
+        let written = parsed.to_bms_text();
+        let reparsed = Briefing::parse_lenient(&written).briefing;
+
+        assert_eq!(
+            serde_json::to_value(&parsed).unwrap(),
+            serde_json::to_value(&reparsed).unwrap(),
+            "{}, written as:\n{}",
+            fixture.display(),
+            written
+        );
+    }
+}
@@ -0,0 +1,62 @@
+//! HTML briefings are turned into the text layout before they're parsed.
+
+use bms_briefing_parser::{html_to_text, Briefing, Format};
+
+#[test]
+fn headings_paragraphs_and_tables() {
+    let html = "<!DOCTYPE html>\n<html><body>\n\
+        <h2>Mission Overview</h2>\n<br>\n<p>Viper1 (Strike)</p>\n<br>\n\
+        <table>\n<tr><td>Pkg-Mission:</td><td>OCA Strike</td></tr>\n</table>\n\
+        </body></html>";
+
+    assert_eq!(Format::detect(html), Format::Html);
+    assert_eq!(
+        html_to_text(html),
+        "Mission Overview\n\t\n\tViper1 (Strike)\n\t\n\tPkg-Mission:\tOCA Strike\n"
+    );
+}
+
+#[test]
+fn tables_parse_into_rows() {
+    let html = "<html><body><h2>Steerpoints</h2><br>\
+        <table><tr><th>#</th><th>Description</th><th>Time</th><th>Dist</th><th>Hdg</th>\
+        <th>CAS</th><th>Alt</th><th>Action</th><th>Form</th><th>Comments</th></tr></table><br>\
+        <table><tr><td>1</td><td>Takeoff</td><td>07:30:00</td><td>0.0</td><td>0</td>\
+        <td>0</td><td>GND</td><td>Takeoff</td><td>--</td><td>--</td></tr>\
+        <tr><td>2</td><td>Nav</td><td>07:42:10</td><td>45.3</td><td>312</td>\
+        <td>350</td><td>FL200</td><td>Nav</td><td>Wedge</td><td>--</td></tr></table>\
+        </body></html>";
+
+    let steerpoints = Briefing::parse_html(html).briefing.steerpoints;
+    assert_eq!(steerpoints.len(), 2);
+    assert_eq!(steerpoints[1].description.as_deref(), Some("Nav"));
+    assert_eq!(steerpoints[1].distance, Some(45.3));
+    assert_eq!(steerpoints[1].altitude.as_ref().unwrap().feet(), 20000);
+}
+
+#[test]
+fn cells_entities_and_blank_lines() {
+    let html = "<html><head><title>x</title></head><body>\
+        <h1>Ordnance</h1><br>\
+        <table><tr><td></td></tr>\
+        <tr><th>Viper1</th><th>--Viper11--</th></tr>\
+        <tr><td>&nbsp;</td><td>2 AIM-9X</td></tr></table>\
+        <!-- a comment <td> --><p>R&amp;R at 12&deg;C &#x2014; &lt;ok&gt;</p>\
+        </body></html>";
+
+    assert_eq!(
+        html_to_text(html),
+        "Ordnance\n\t\n\t\n\tViper1\t--Viper11--\n\t\t2 AIM-9X\n\tR&R at 12°C \u{2014} <ok>\n"
+    );
+}
+
+#[test]
+fn pre_is_taken_as_is() {
+    let html = "<html><body><pre>Situation:\n\t\n\tAll clear &amp; quiet.\n</pre></body></html>";
+
+    assert_eq!(html_to_text(html), "Situation:\n\t\n\tAll clear & quiet.\n");
+    assert_eq!(
+        Briefing::parse_html(html).briefing.sitrep.text,
+        "All clear & quiet."
+    );
+}
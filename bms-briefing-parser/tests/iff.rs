@@ -0,0 +1,108 @@
+use bms_briefing_parser::{
+    Briefing, CodeChange, IffCodes, IffMode, MissionTime, Parsed, SectionKind,
+};
+
+const IFF: &str = "Iff:
+\t
+\tInitial settings
+\tInitial:\tModes active: 1,3\tCodes:\tM1: 12\tM3: 4400
+\tPolicy:\tM1: ON\tM3: ON
+\tCode change:\tAutomatic
+\t
+\tTime events
+\t\tM1\tM3
+\t08:00:00\t13\t4401
+\t09:30:00\t14\t4402
+\tPosition events:\tSTPT 3: M3 4410
+";
+
+const MALFORMED: &str = "Iff:
+\t
+\tInitial settings
+\tInitial:\tModes active: 1,3\tCodes:\tM1: 12\tM3: 4489
+\tPolicy:\tM1: ON\tM3: STBY
+\tCode change:\tAutomatic
+\t
+\tTime events
+\t\tM1\tM3
+\t08:00:00\t13\t4401
+\t0800\t14\t4402
+\tPosition events:\tSTPT 2 M3 4410
+";
+
+fn warnings<'a>(parsed: &'a Parsed) -> Vec<(Option<usize>, &'a str)> {
+    parsed
+        .diagnostics
+        .iter()
+        .filter(|d| d.section == SectionKind::Iff)
+        .map(|d| (d.line, d.message.as_str()))
+        .collect()
+}
+
+fn codes(codes: &IffCodes) -> (Option<String>, Option<String>) {
+    (
+        codes.m1.map(|c| c.to_string()),
+        codes.m3.map(|c| c.to_string()),
+    )
+}
+
+fn time(text: &str) -> MissionTime {
+    text.parse().unwrap()
+}
+
+#[test]
+fn initial_settings() {
+    let iff = Briefing::parse_lenient(IFF).briefing.iff;
+
+    assert_eq!(iff.initial.modes_active, [IffMode::M1, IffMode::M3]);
+    assert_eq!(
+        codes(&iff.initial.codes),
+        (Some("12".into()), Some("4400".into()))
+    );
+    assert_eq!(iff.initial.codes.m3.unwrap().value(), 0o4400);
+    assert_eq!(iff.initial.policy.get(IffMode::M3), Some(true));
+    assert_eq!(iff.initial.policy.get(IffMode::M2), None);
+    assert_eq!(iff.initial.code_change, Some(CodeChange::Automatic));
+}
+
+#[test]
+fn time_and_position_events() {
+    let iff = Briefing::parse_lenient(IFF).briefing.iff;
+
+    assert_eq!(iff.time_events.len(), 2);
+    assert_eq!(
+        codes(&iff.codes_at(&time("07:00:00"))),
+        (Some("12".into()), Some("4400".into()))
+    );
+    assert_eq!(
+        codes(&iff.codes_at(&time("09:30:00"))),
+        (Some("14".into()), Some("4402".into()))
+    );
+    let next = iff.next_change(&time("08:00:00")).unwrap();
+    assert_eq!(next.time, time("09:30:00"));
+
+    let event = &iff.position_events[0];
+    assert_eq!(event.steerpoint, 3);
+    assert_eq!(codes(&event.codes), (None, Some("4410".into())));
+}
+
+#[test]
+fn invalid_values_are_left_out_with_a_warning() {
+    let parsed = Briefing::parse_lenient(MALFORMED);
+    let iff = &parsed.briefing.iff;
+
+    assert_eq!(codes(&iff.initial.codes), (Some("12".into()), None));
+    assert_eq!(iff.initial.policy.get(IffMode::M1), Some(true));
+    assert_eq!(iff.initial.policy.get(IffMode::M3), None);
+    assert_eq!(iff.time_events.len(), 1);
+    assert!(iff.position_events.is_empty());
+    assert_eq!(
+        warnings(&parsed),
+        [
+            (Some(4), "invalid IFF code `4489`"),
+            (Some(5), "invalid IFF policy `STBY`"),
+            (Some(11), "invalid IFF row `0800`"),
+            (Some(12), "invalid position event `STPT 2 M3 4410`"),
+        ]
+    );
+}
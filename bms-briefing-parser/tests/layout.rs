@@ -0,0 +1,80 @@
+//! Layouts the parser doesn't know leave the sections that depend on them empty.
+
+use bms_briefing_parser::{BmsFormatVersion, Briefing, SectionKind, Severity};
+
+fn unsupported(text: &str) -> Vec<SectionKind> {
+    Briefing::parse_lenient(text)
+        .diagnostics
+        .iter()
+        .filter(|d| d.message.contains("unsupported BMS version"))
+        .inspect(|d| assert_eq!(d.severity, Severity::Error))
+        .map(|d| d.section)
+        .collect()
+}
+
+#[test]
+fn fixtures_are_the_latest_layout() {
+    let text = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/synthetic/kto-campaign-strike-4ship.txt"
+    ))
+    .unwrap();
+    let parsed = Briefing::parse_lenient(&text);
+    assert_eq!(parsed.version, Some(BmsFormatVersion::Bms435));
+    assert_eq!(parsed.diagnostics, vec![]);
+}
+
+#[test]
+fn unknown_layout_leaves_tables_empty() {
+    // Column names right under the header, and rows with one column too many.
+    let text = "Situation:\n\
+        \t\n\
+        \tAll quiet.\n\
+        Steerpoints:\n\
+        \t#\tDescription\tTime\tDist\tHdg\tCAS\tAlt\tAction\tForm\tComments\tFuel\n\
+        \t\n\
+        \t1\tTakeoff\t07:30:00\t0.0\t0\t0\tGND\tTakeoff\t--\t--\t8000\n";
+
+    let parsed = Briefing::parse_lenient(text);
+    assert_eq!(parsed.version, None);
+    assert_eq!(parsed.briefing.sitrep.text, "All quiet.");
+    assert!(parsed.briefing.steerpoints.is_empty());
+    assert_eq!(unsupported(text), [SectionKind::Steerpoints]);
+}
+
+#[test]
+fn unknown_weather_layout_leaves_weather_empty() {
+    // Wind layers right under the header, without the blank line.
+    let text = "Situation:\n\
+        \t\n\
+        \tAll quiet.\n\
+        Weather:\n\
+        \tAltitude\tWind\tTemperature\n\
+        \tSurface\t270@08kts\t14C\n";
+
+    let parsed = Briefing::parse_lenient(text);
+    assert_eq!(parsed.version, None);
+    assert_eq!(parsed.briefing.sitrep.text, "All quiet.");
+    assert!(parsed.briefing.weather.winds.is_empty());
+    assert_eq!(unsupported(text), [SectionKind::Weather]);
+}
+
+#[test]
+fn other_sections_are_read_when_one_deviates() {
+    let text = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/synthetic/kto-campaign-strike-4ship.txt"
+    ))
+    .unwrap();
+    // Drop the blank line under the weather header.
+    let text = text.replace("Weather:\n\t\n", "Weather:\n");
+
+    let parsed = Briefing::parse_lenient(&text);
+    assert_eq!(parsed.version, Some(BmsFormatVersion::Bms435));
+    assert!(parsed.briefing.weather.winds.is_empty());
+    assert!(!parsed.briefing.steerpoints.is_empty());
+    assert!(!parsed.briefing.comms.is_empty());
+    assert!(!parsed.briefing.ordnance.flights.is_empty());
+    assert!(!parsed.briefing.support.assets.is_empty());
+    assert_eq!(unsupported(&text), [SectionKind::Weather]);
+}
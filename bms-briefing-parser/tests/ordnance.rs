@@ -0,0 +1,77 @@
+use bms_briefing_parser::{AircraftLoadout, Briefing};
+
+const ORDNANCE: &str = "Ordnance:
+\t
+\t
+\tViper1\t--Viper11--\t--Viper12--
+\t4000000000 Chaff\t4000000000 Chaff
+\t600 Gal Tank\t1 600 Gal Tank
+";
+
+#[test]
+fn totals_saturate() {
+    let briefing = Briefing::parse_lenient(ORDNANCE).briefing;
+    let totals = briefing.ordnance.flights[0].totals();
+
+    assert_eq!(totals[0].name, "Chaff");
+    assert_eq!(totals[0].quantity, u32::MAX);
+}
+
+#[test]
+fn fuel_tanks_are_not_counted_in_gallons() {
+    let briefing = Briefing::parse_lenient(ORDNANCE).briefing;
+    let totals = briefing.ordnance.flights[0].totals();
+
+    assert_eq!(totals[1].name, "600 Gal Tank");
+    assert_eq!(totals[1].quantity, 2);
+}
+
+const LOADOUTS: &str = "Ordnance:
+\t
+\t
+\tWeasel1\t--Weasel11--\t--Weasel12--\t--Weasel13--\t--Weasel14--
+\t2 AGM-88C\t2 AGM-88C\t2 AGM-88C\t2 AGM-88C
+\t\t1 HTS pod\t\t1 HTS pod
+\t2 370 Gal Tank\t2 370 Gal Tank\t2 370 Gal Tank\t2 370 Gal Tank
+\t
+\tHog2\t--Hog21--\t--Hog22--
+\t4x Mk-82\t4x Mk-82
+\t1 2000 lb GBU-31\t1 2000 lb GBU-31
+";
+
+fn stations<'a>(loadout: &'a AircraftLoadout) -> Vec<(u32, &'a str)> {
+    loadout
+        .stations
+        .iter()
+        .map(|s| (s.quantity, s.name.as_ref()))
+        .collect()
+}
+
+#[test]
+fn empty_cells_leave_the_station_out() {
+    let briefing = Briefing::parse_lenient(LOADOUTS).briefing;
+    let weasels = &briefing.ordnance.flights[0];
+
+    assert_eq!(weasels.callsign, "Weasel1");
+    assert_eq!(
+        stations(&weasels.aircraft[0]),
+        [(2, "AGM-88C"), (2, "370 Gal Tank")]
+    );
+    assert_eq!(
+        stations(&weasels.aircraft[1]),
+        [(2, "AGM-88C"), (1, "HTS pod"), (2, "370 Gal Tank")]
+    );
+}
+
+#[test]
+fn counts_and_weights() {
+    let briefing = Briefing::parse_lenient(LOADOUTS).briefing;
+    let hogs = &briefing.ordnance.flights[1];
+
+    assert_eq!(hogs.callsign, "Hog2");
+    assert_eq!(hogs.aircraft[1].callsign, "Hog22");
+    assert_eq!(
+        stations(&hogs.aircraft[1]),
+        [(4, "Mk-82"), (1, "2000 lb GBU-31")]
+    );
+}
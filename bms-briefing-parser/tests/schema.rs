@@ -0,0 +1,152 @@
+//! Checks that the snapshots in `tests/synthetic/` load back into the parser
+//! types and match `schema/briefing.v1.schema.json`.
+//!
+//! Objects may not have properties the schema doesn't list, so renaming a
+//! field without updating the schema fails here.
+
+use std::{env, fs, path::PathBuf};
+
+use bms_briefing_parser::{Parsed, SCHEMA, SCHEMA_VERSION};
+use serde_json::Value;
+
+fn snapshots() -> Vec<PathBuf> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/synthetic");
+
+    let mut snapshots: Vec<PathBuf> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|e| e == "json"))
+        .collect();
+    snapshots.sort();
+
+    snapshots
+}
+
+#[test]
+fn snapshots_round_trip() {
+    for snapshot in snapshots() {
+        let expected = fs::read_to_string(&snapshot).unwrap();
+
+        let parsed: Parsed = serde_json::from_str(&expected)
+            .unwrap_or_else(|e| panic!("{}: {}", snapshot.display(), e));
+        let actual = serde_json::to_string_pretty(&parsed).unwrap() + "\n";
+
+        assert!(
+            expected == actual,
+            "{}: changed after loading",
+            snapshot.display()
+        );
+    }
+}
+
+#[test]
+fn snapshots_match_schema() {
+    let schema: Value = serde_json::from_str(SCHEMA).unwrap();
+    assert_eq!(schema["version"], SCHEMA_VERSION);
+
+    for snapshot in snapshots() {
+        let value: Value = serde_json::from_str(&fs::read_to_string(&snapshot).unwrap()).unwrap();
+
+        let mut errors = vec![];
+        let parsed = &schema["$defs"]["Parsed"];
+        validate(&schema, parsed, &value, "", &mut errors);
+
+        assert!(
+            errors.is_empty(),
+            "{} doesn't match the schema:\n{}",
+            snapshot.display(),
+            errors.join("\n")
+        );
+    }
+}
+
+/// Just enough of JSON schema for the briefing schema.
+fn validate(root: &Value, schema: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+    if let Some(reference) = schema["$ref"].as_str() {
+        let target = match reference.strip_prefix("#/$defs/") {
+            Some(name) => &root["$defs"][name],
+            None => root,
+        };
+        return validate(root, target, value, path, errors);
+    }
+
+    for key in ["anyOf", "oneOf"] {
+        if let Some(options) = schema[key].as_array() {
+            let matches = options.iter().any(|option| {
+                let mut option_errors = vec![];
+                validate(root, option, value, path, &mut option_errors);
+                option_errors.is_empty()
+            });
+            if !matches {
+                errors.push(format!("{}: no option of {} matches {}", path, key, value));
+            }
+            return;
+        }
+    }
+
+    let types: Vec<&str> = match &schema["type"] {
+        Value::String(t) => vec![t],
+        Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+        _ => vec![],
+    };
+    let type_matches = |t: &str| match t {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => false,
+    };
+    if !types.is_empty() && !types.into_iter().any(type_matches) {
+        errors.push(format!("{}: unexpected {}", path, value));
+        return;
+    }
+
+    if let Some(values) = schema["enum"].as_array() {
+        if !value.is_null() && !values.contains(value) {
+            errors.push(format!("{}: {} is not one of {:?}", path, value, values));
+        }
+    }
+
+    if let (Some(minimum), Some(n)) = (schema["minimum"].as_f64(), value.as_f64()) {
+        if n < minimum {
+            errors.push(format!("{}: {} is below {}", path, n, minimum));
+        }
+    }
+    if let (Some(maximum), Some(n)) = (schema["maximum"].as_f64(), value.as_f64()) {
+        if n > maximum {
+            errors.push(format!("{}: {} is above {}", path, n, maximum));
+        }
+    }
+
+    if let Some(items) = value.as_array() {
+        for (i, item) in items.iter().enumerate() {
+            validate(
+                root,
+                &schema["items"],
+                item,
+                &format!("{}/{}", path, i),
+                errors,
+            );
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        let properties = &schema["properties"];
+        for (key, value) in object {
+            match properties.get(key) {
+                Some(property) => {
+                    validate(root, property, value, &format!("{}/{}", path, key), errors)
+                }
+                None => errors.push(format!("{}: `{}` is not in the schema", path, key)),
+            }
+        }
+        for required in schema["required"].as_array().into_iter().flatten() {
+            if !object.contains_key(required.as_str().unwrap()) {
+                errors.push(format!("{}: missing `{}`", path, required));
+            }
+        }
+    }
+}
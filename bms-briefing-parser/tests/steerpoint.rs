@@ -0,0 +1,102 @@
+use bms_briefing_parser::{AltitudeKind, Briefing, MissionTime, Parsed, SectionKind};
+
+const STEERPOINTS: &str = "Steerpoints:
+\t
+\t#\tDescription\tTime\tDist\tHdg\tCAS\tAlt\tAction\tForm\tComments
+\t
+\t1\tTakeoff\t22:30:00\t0.0\t0\t0\tGND\tTakeoff\t--\t--
+\t2\tNav\t23:05\t45.3\t312\t350\t20000ft\tNav\tWedge\t--
+\t3\tPush\t23:40:00\t60.1\t330\t380\tFL20\tNav\tSpread\tLow level
+\t4\tTarget\t00:15:00\t38.2\t341\t420\t500 AGL\tStrike\tSpread\tPast midnight
+\t5\tNav\t24:59:59\t30.0\t160\t400\tFL2OO\tNav\tSpread\t--
+\t6\tNav\t9.30\t41.0\t165\t350\tFL99999999\tNav\tWedge\t--
+";
+
+fn warnings<'a>(parsed: &'a Parsed) -> Vec<(Option<usize>, &'a str)> {
+    parsed
+        .diagnostics
+        .iter()
+        .filter(|d| d.section == SectionKind::Steerpoints)
+        .map(|d| (d.line, d.message.as_str()))
+        .collect()
+}
+
+#[test]
+fn times_and_altitudes_keep_their_text() {
+    let steerpoints = Briefing::parse_lenient(STEERPOINTS).briefing.steerpoints;
+
+    let time = steerpoints[1].time.as_ref().unwrap();
+    assert_eq!(time.text(), "23:05");
+    assert_eq!((time.hours(), time.minutes(), time.seconds()), (23, 5, 0));
+
+    let altitude = steerpoints[1].altitude.as_ref().unwrap();
+    assert_eq!(altitude.text(), "20000ft");
+    assert_eq!(altitude.feet(), 20_000);
+
+    let altitude = steerpoints[2].altitude.as_ref().unwrap();
+    assert_eq!(altitude.text(), "FL20");
+    assert_eq!(altitude.kind, AltitudeKind::FlightLevel);
+    assert_eq!(altitude.feet(), 2_000);
+
+    let altitude = steerpoints[3].altitude.as_ref().unwrap();
+    assert_eq!(altitude.kind, AltitudeKind::Agl);
+    assert_eq!(altitude.feet(), 500);
+    assert!(steerpoints[0].altitude.as_ref().unwrap().is_ground());
+}
+
+#[test]
+fn times_past_midnight_are_on_the_next_day() {
+    let steerpoints = Briefing::parse_lenient(STEERPOINTS).briefing.steerpoints;
+
+    let time = steerpoints[3].time.as_ref().unwrap();
+    assert_eq!(time.day(), 1);
+    assert_eq!(time.as_seconds(), 24 * 3600 + 15 * 60);
+    assert_eq!(time.text(), "00:15:00");
+}
+
+#[test]
+fn invalid_values_are_left_out_with_a_warning() {
+    let parsed = Briefing::parse_lenient(STEERPOINTS);
+    let steerpoints = &parsed.briefing.steerpoints;
+
+    assert_eq!(steerpoints[4].time, None);
+    assert_eq!(steerpoints[4].altitude, None);
+    assert_eq!(steerpoints[5].time, None);
+    assert_eq!(steerpoints[5].altitude, None);
+    assert_eq!(
+        warnings(&parsed),
+        [
+            (Some(9), "invalid time `24:59:59`"),
+            (Some(9), "invalid altitude `FL2OO`"),
+            (Some(10), "invalid time `9.30`"),
+            (Some(10), "invalid altitude `FL99999999`"),
+        ]
+    );
+
+    // Parsed briefings serialize without overflowing on the way.
+    serde_json::to_string(&parsed).unwrap();
+}
+
+#[test]
+fn end_of_day_is_the_only_hour_24() {
+    let end: MissionTime = "24:00:00".parse().unwrap();
+    assert_eq!(end.as_seconds(), 24 * 3600);
+
+    for text in ["24:00:01", "24:01", "25:00:00"] {
+        assert!(text.parse::<MissionTime>().is_err(), "{}", text);
+    }
+}
+
+#[test]
+fn huge_times_are_none() {
+    assert_eq!(MissionTime::from_hms(u32::MAX, 0, 0), None);
+    assert_eq!(MissionTime::from_hms(0, u32::MAX, 0), None);
+    assert_eq!(
+        MissionTime::from_hms(0, 0, u32::MAX).unwrap().as_seconds(),
+        u32::MAX
+    );
+
+    let late = MissionTime::from_seconds(u32::MAX);
+    let time = MissionTime::from_hms(7, 30, 0).unwrap().after(&late);
+    assert!(time >= MissionTime::from_hms(7, 30, 0).unwrap());
+}
@@ -0,0 +1,76 @@
+use bms_briefing_parser::{Band, Briefing, Parsed, SectionKind, SupportRole, TacanBand};
+
+const SUPPORT: &str = "Support:
+\t
+\tCallsign\tType\tDescription
+\t
+\tShell1:\tTanker\tKC-10, UHF 276.000 (11), TACAN 31Y, Track STPT 8
+\tOn station 22:00 - 00:30
+\tArco2:\tTanker\tKC-135, UHF 412.500 (4), TACAN 140X
+\tOrbit east of the FLOT, 08:00:00 - 10:00
+\tDarkstar1:\tJSTARS\tE-8C, VHF 138.000
+";
+
+fn warnings<'a>(parsed: &'a Parsed) -> Vec<(Option<usize>, &'a str)> {
+    parsed
+        .diagnostics
+        .iter()
+        .filter(|d| d.section == SectionKind::Support)
+        .map(|d| (d.line, d.message.as_str()))
+        .collect()
+}
+
+#[test]
+fn descriptions_continue_on_the_next_line() {
+    let support = Briefing::parse_lenient(SUPPORT).briefing.support;
+    let shell = &support.assets[0];
+
+    assert_eq!(shell.role, SupportRole::Tanker);
+    assert_eq!(shell.callsign, "Shell1");
+    assert_eq!(shell.aircraft.as_deref(), Some("KC-10"));
+
+    let frequency = shell.frequency.unwrap();
+    assert_eq!(frequency.band(), Band::Uhf);
+    assert_eq!(
+        (frequency.khz(), frequency.preset()),
+        (Some(276_000), Some(11))
+    );
+
+    let tacan = shell.tacan.unwrap();
+    assert_eq!((tacan.channel, tacan.band), (31, TacanBand::Y));
+    assert_eq!(shell.track.as_deref(), Some("STPT 8"));
+
+    // The window spans midnight.
+    let window = shell.on_station.as_ref().unwrap();
+    assert_eq!(window.start.as_seconds(), 22 * 3600);
+    assert_eq!(window.end.as_seconds(), 24 * 3600 + 30 * 60);
+    assert!(shell.notes.is_empty());
+
+    assert_eq!(support.tankers().count(), 2);
+}
+
+#[test]
+fn invalid_values_are_left_out_with_a_warning() {
+    let parsed = Briefing::parse_lenient(SUPPORT);
+    let arco = &parsed.briefing.support.assets[1];
+
+    assert_eq!(arco.frequency, None);
+    assert_eq!(arco.tacan, None);
+    assert_eq!(arco.notes, ["Orbit east of the FLOT"]);
+    let window = arco.on_station.as_ref().unwrap();
+    assert_eq!(
+        (window.start.as_seconds(), window.end.as_seconds()),
+        (8 * 3600, 10 * 3600)
+    );
+    assert_eq!(
+        warnings(&parsed),
+        [
+            (Some(7), "invalid frequency `412.500 (4)`"),
+            (Some(7), "invalid TACAN channel `140X`"),
+        ]
+    );
+
+    let darkstar = &parsed.briefing.support.assets[2];
+    assert_eq!(darkstar.role, SupportRole::Jstars);
+    assert_eq!(darkstar.frequency.unwrap().mhz(), Some(138.0));
+}
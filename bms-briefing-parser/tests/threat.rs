@@ -0,0 +1,113 @@
+//! Threat analysis lines are matched against the bundled threat database.
+
+use std::borrow::Cow;
+
+use bms_briefing_parser::{Briefing, LocationReference, SectionKind, ThreatDatabase};
+
+fn threats(analysis: &str) -> Vec<String> {
+    let text = format!("Threat Analysis:\n\t\n\t{}\n", analysis);
+    let briefing = Briefing::parse_lenient(&text).briefing;
+    briefing
+        .threat_analysis
+        .threats
+        .iter()
+        .map(|t| t.system.to_string())
+        .collect()
+}
+
+#[test]
+fn plain_names_need_a_system_word() {
+    assert_eq!(
+        threats("Hawk flight checks in at STPT 3."),
+        Vec::<String>::new()
+    );
+    assert_eq!(threats("Hawk battery near STPT 3."), ["Hawk"]);
+    assert_eq!(threats("Tor site near the target."), ["Tor"]);
+    assert_eq!(threats("SA-15 near the target."), ["SA-15"]);
+}
+
+#[test]
+fn details_borrow_the_database() {
+    let text = "Threat Analysis:\n\t\n\tSA-6 near STPT 4.\n";
+    let briefing = Briefing::parse_lenient(text).briefing;
+
+    let details = &briefing.threat_analysis.threats[0].details;
+    assert!(matches!(details, Some(Cow::Borrowed(system)) if system.name == "SA-6"));
+}
+
+const ANALYSIS: &str = "Threat Analysis:
+\t
+\tSA-99 reported near STPT 4.
+\tHeavy AAA around the target.
+\tHawk battery 10nm east of STPT 3.
+\tHawk flight will check in on the tower frequency.
+\tSA-6 launchers moved 5nm south of the route.
+";
+
+#[test]
+fn locations_are_relative_to_a_reference() {
+    let analysis = Briefing::parse_lenient(ANALYSIS).briefing.threat_analysis;
+
+    let locations: Vec<_> = analysis
+        .threats
+        .iter()
+        .map(|t| {
+            let location = t.location.as_ref().unwrap();
+            (
+                t.system.as_ref(),
+                location.distance,
+                location.direction.as_deref(),
+                location.reference,
+            )
+        })
+        .collect();
+    assert_eq!(
+        locations,
+        [
+            ("SA-99", None, None, Some(LocationReference::Steerpoint(4))),
+            (
+                "Hawk",
+                Some(10.0),
+                Some("east"),
+                Some(LocationReference::Steerpoint(3))
+            ),
+            (
+                "SA-6",
+                Some(5.0),
+                Some("south"),
+                Some(LocationReference::Route)
+            ),
+        ]
+    );
+    assert_eq!(
+        analysis.threats[1]
+            .details
+            .as_ref()
+            .map(|d| d.name.as_ref()),
+        Some("MIM-23")
+    );
+}
+
+#[test]
+fn unknown_systems_are_kept_with_a_warning() {
+    let parsed = Briefing::parse_lenient(ANALYSIS);
+    let analysis = &parsed.briefing.threat_analysis;
+
+    assert!(analysis.threats[0].details.is_none());
+    assert_eq!(
+        analysis.remarks,
+        [
+            "Heavy AAA around the target.",
+            "Hawk flight will check in on the tower frequency.",
+        ]
+    );
+    assert_eq!(analysis.database, ThreatDatabase::bundled().version());
+
+    let warnings: Vec<_> = parsed
+        .diagnostics
+        .iter()
+        .filter(|d| d.section == SectionKind::ThreatAnalysis)
+        .map(|d| (d.line, d.message.as_str()))
+        .collect();
+    assert_eq!(warnings, [(Some(3), "unknown threat system `SA-99`")]);
+}
@@ -0,0 +1,92 @@
+use bms_briefing_parser::{Briefing, Parsed, SectionKind};
+
+const WEATHER: &str = "Weather:
+\t
+\tAltitude\tWind\tTemperature
+\tSurface\t220@12kts\t24C
+\tFL100\tVRB@05kts\t4C
+\tFL200\t250@38kts\t-16C
+\tPressure:\t29.92 inHg / 1013 hPa
+\tVisibility:\t8 km
+\tClouds:\tBase 6000ft\tTops 7500ft
+\tContrails:\tFL260
+";
+
+const MALFORMED: &str = "Weather:
+\t
+\tAltitude\tWind\tTemperature
+\tSurface\tCalm\t21C
+\tFL100\t090-15kts\t2C
+\tPressure:\t29,92 inHg
+\tVisibility:\t15 nm
+\tClouds:\tBase FL99999999
+\tHumidity:\t80%
+";
+
+fn warnings<'a>(parsed: &'a Parsed) -> Vec<(Option<usize>, &'a str)> {
+    parsed
+        .diagnostics
+        .iter()
+        .filter(|d| d.section == SectionKind::Weather)
+        .map(|d| (d.line, d.message.as_str()))
+        .collect()
+}
+
+#[test]
+fn wind_layers() {
+    let weather = Briefing::parse_lenient(WEATHER).briefing.weather;
+
+    let winds: Vec<_> = weather
+        .winds
+        .iter()
+        .map(|w| (w.altitude.feet(), w.direction, w.speed, w.temperature))
+        .collect();
+    assert_eq!(
+        winds,
+        [
+            (0, Some(220), 12, Some(24)),
+            (10_000, None, 5, Some(4)),
+            (20_000, Some(250), 38, Some(-16)),
+        ]
+    );
+    assert!(weather.surface().unwrap().altitude.is_ground());
+}
+
+#[test]
+fn pressure_visibility_and_clouds() {
+    let weather = Briefing::parse_lenient(WEATHER).briefing.weather;
+
+    let pressure = weather.pressure.unwrap();
+    assert_eq!((pressure.inhg, pressure.hpa), (29.92, 1013.0));
+    // 8 km in nautical miles.
+    assert_eq!(weather.visibility, Some(4.3));
+    assert_eq!(weather.cloud_base.map(|a| a.feet()), Some(6_000));
+    assert_eq!(weather.cloud_tops.map(|a| a.feet()), Some(7_500));
+    assert_eq!(weather.contrails.map(|a| a.feet()), Some(26_000));
+    assert_eq!(
+        weather.summary.as_deref(),
+        Some("22012KT 4.3NM BASE060 TOPS075 24C A2992 Q1013")
+    );
+}
+
+#[test]
+fn malformed_rows_are_left_out_with_a_warning() {
+    let parsed = Briefing::parse_lenient(MALFORMED);
+    let weather = &parsed.briefing.weather;
+
+    assert_eq!(weather.winds.len(), 1);
+    assert_eq!(weather.surface().unwrap().speed, 0);
+    assert_eq!(weather.pressure, None);
+    assert_eq!(weather.visibility, Some(15.0));
+    assert_eq!(weather.cloud_base, None);
+    assert_eq!(weather.summary.as_deref(), Some("00000KT 15NM 21C"));
+    assert_eq!(
+        warnings(&parsed),
+        [
+            (Some(5), "invalid wind `090-15kts`"),
+            (Some(6), "invalid pressure `29,92 inHg`"),
+            (Some(8), "invalid altitude `FL99999999`"),
+            (Some(9), "unknown weather row `Humidity:\t80%`"),
+        ]
+    );
+}
@@ -0,0 +1,72 @@
+//! Briefings written back out keep what was parsed from them.
+
+use bms_briefing_parser::{Briefing, Dictionary};
+
+#[test]
+fn distances_are_written_as_parsed() {
+    let text = "Steerpoints:\n\t\n\t#\tDescription\tTime\tDist\tHdg\tCAS\tAlt\tAction\tForm\tComments\n\t\n\
+        \t1\tTakeoff\t07:30:00\t0.0\t0\t0\tGND\tTakeoff\t--\t--\n\
+        \t2\tNav\t07:42:10\t45.25\t312\t350\t20000\tNav\tWedge\t--\n";
+    let written = Briefing::parse_lenient(text).briefing.to_bms_text();
+
+    assert!(
+        written.contains("\t1\tTakeoff\t07:30:00\t0.0\t"),
+        "{}",
+        written
+    );
+    assert!(
+        written.contains("\t2\tNav\t07:42:10\t45.25\t"),
+        "{}",
+        written
+    );
+}
+
+#[test]
+fn empty_overview_values_leave_out_parentheses() {
+    let text = "Mission Overview:\n\t\n\tViper1\n\t\n\tPackage: 6542\n";
+    let briefing = Briefing::parse_lenient(text).briefing;
+    assert_eq!(briefing.overview.mission_type, "");
+
+    let written = briefing.to_bms_text();
+    assert!(written.contains("\tViper1\n"), "{}", written);
+    assert!(written.contains("\tPackage: 6542\n"), "{}", written);
+}
+
+#[test]
+fn briefings_are_written_in_the_dictionary_language() {
+    let text = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/synthetic/kto-campaign-strike-4ship-de.txt"
+    ))
+    .unwrap();
+    let briefing = Briefing::parse_lenient(&text).briefing;
+
+    let written = briefing.to_bms_text_with(Dictionary::german());
+    assert!(written.contains("Wetter:\n"), "{}", written);
+    assert!(written.contains("\tBoden\t"), "{}", written);
+    assert_eq!(Dictionary::detect(&written).language(), "Deutsch");
+
+    let reparsed = Briefing::parse_lenient(&written).briefing;
+    assert_eq!(
+        serde_json::to_value(&briefing).unwrap(),
+        serde_json::to_value(&reparsed).unwrap()
+    );
+}
+
+#[test]
+fn callsigns_are_written_as_printed() {
+    let text = "Comm Ladder:\n\t\n\tAgency\tCallsign\tUHF\tVHF\tNotes\n\t\n\
+        \tTower:\tKunsan Tower [11]\t292.300 (1)\t126.200\t--\n";
+    let briefing = Briefing::parse_lenient(text).briefing;
+
+    let comm = &briefing.comms[0];
+    assert_eq!(comm.callsign.as_deref(), Some("Kunsan Tower"));
+    assert_eq!(comm.raw_callsign.as_deref(), Some("Kunsan Tower [11]"));
+
+    let written = briefing.to_bms_text();
+    assert!(
+        written.contains("\tTower:\tKunsan Tower [11]\t"),
+        "{}",
+        written
+    );
+}
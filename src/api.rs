@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::Path,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use bms_briefing_parser::{Briefing, Diagnostic, Parsed};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    cache::{Cache, Snapshot, State},
+    conditional::conditional,
+    error::{Error, ErrorKind},
+    sections,
+};
+
+/// Every API response says whether there is a briefing yet, in `status`.
+#[derive(Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum Status<T> {
+    WaitingForBms { message: &'static str },
+    WaitingForBriefing { message: &'static str },
+    Ready(T),
+    Error(Error),
+}
+
+#[derive(Serialize)]
+struct Section {
+    section: String,
+    data: Value,
+    /// What the parser had to say about the section.
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// `/api/briefing`, the parsed briefing with its diagnostics.
+pub async fn briefing(Extension(cache): Extension<Arc<Cache>>, headers: HeaderMap) -> Response {
+    respond(&cache.get(), &headers, "/api/briefing", |parsed| {
+        serde_json::to_value(parsed).unwrap_or_default()
+    })
+}
+
+/// `/api/briefing/{section}`, one section of the briefing by its JSON name.
+pub async fn section(
+    Extension(cache): Extension<Arc<Cache>>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    let names = sections();
+    if !names.contains(&name) {
+        let message = format!("Unknown section `{}`", name);
+        return error(Error::new(ErrorKind::UnknownSection, message).with_details(names));
+    }
+
+    let kind = sections::Section::find(&name).map(sections::Section::kind);
+    let representation = format!("/api/briefing/{}", name);
+    respond(&cache.get(), &headers, &representation, |parsed| {
+        let mut briefing = serde_json::to_value(&parsed.briefing).unwrap_or_default();
+        Section {
+            data: briefing[&name].take(),
+            diagnostics: parsed
+                .diagnostics
+                .iter()
+                .filter(|d| Some(d.section) == kind)
+                .cloned()
+                .collect(),
+            section: name,
+        }
+    })
+}
+
+/// Anything else under `/api`.
+pub async fn not_found(Path(path): Path<String>) -> Response {
+    error(Error::new(
+        ErrorKind::NotFound,
+        format!("No API endpoint at /api/{}", path),
+    ))
+}
+
+/// The names of the sections, as they are in the briefing's JSON.
+fn sections() -> Vec<String> {
+    match serde_json::to_value(Briefing::default()) {
+        Ok(Value::Object(fields)) => fields.keys().cloned().collect(),
+        _ => vec![],
+    }
+}
+
+fn error(error: Error) -> Response {
+    (error.status(), Json(Status::<()>::Error(error))).into_response()
+}
+
+/// What `data` picks from the briefing, or why there is no briefing yet.
+///
+/// `representation` is the route, see [`Snapshot::etag`].
+fn respond<T: Serialize>(
+    snapshot: &Snapshot,
+    headers: &HeaderMap,
+    representation: &str,
+    data: impl FnOnce(&Parsed<'static>) -> T,
+) -> Response {
+    let state = &snapshot.state;
+    let status = match state {
+        State::Ready(parsed) => {
+            let modified = snapshot.source.and_then(|s| s.modified);
+            return conditional(headers, snapshot.etag(representation), modified, || {
+                Json(Status::Ready(data(parsed))).into_response()
+            });
+        }
+        State::Failed(e) => return error(e.clone()),
+        State::WaitingForBms => Status::<T>::WaitingForBms {
+            message: state.message(),
+        },
+        State::WaitingForBriefing => Status::WaitingForBriefing {
+            message: state.message(),
+        },
+    };
+
+    (StatusCode::SERVICE_UNAVAILABLE, Json(status)).into_response()
+}
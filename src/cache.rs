@@ -0,0 +1,227 @@
+use std::{
+    collections::HashMap,
+    mem::discriminant,
+    sync::{Arc, Mutex, RwLock},
+    time::SystemTime,
+};
+
+use bms_briefing_parser::{Briefing, Parsed};
+
+use crate::{
+    conditional::etag,
+    error::{Error, ErrorKind},
+    latest_briefing, Options,
+};
+
+/// The briefing, or what the server is still waiting for before it has one.
+pub enum State {
+    WaitingForBms,
+    WaitingForBriefing,
+    Ready(Box<Parsed<'static>>),
+    Failed(Error),
+}
+
+/// The briefing file a state was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Source {
+    /// Of the file's contents, BMS often writes the same briefing again.
+    pub hash: u64,
+    pub modified: Option<SystemTime>,
+}
+
+impl Source {
+    fn new(bytes: &[u8], modified: Option<SystemTime>) -> Self {
+        Self {
+            hash: content_hash(bytes),
+            modified,
+        }
+    }
+}
+
+/// Hash of `bytes` and the server version, pages change with either.
+///
+/// 64-bit FNV-1a, so ETags stay the same across restarts and Rust releases.
+pub fn content_hash(bytes: &[u8]) -> u64 {
+    let version = env!("CARGO_PKG_VERSION").as_bytes();
+
+    // The version can't contain a 0 byte, so it can't run into the content.
+    fnv1a(FNV_OFFSET, version.iter().chain(&[0]).chain(bytes))
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+fn fnv1a<'a>(hash: u64, bytes: impl IntoIterator<Item = &'a u8>) -> u64 {
+    bytes.into_iter().fold(hash, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+impl State {
+    /// Reads and parses whichever briefing BMS wrote last.
+    pub fn load(options: &Options) -> (Self, Option<Source>) {
+        let briefing_dir = options.briefing_dir.read().unwrap();
+        let Some(briefing_dir) = briefing_dir.as_ref() else {
+            return (State::WaitingForBms, None);
+        };
+        let Some(path) = latest_briefing(briefing_dir) else {
+            return (State::WaitingForBriefing, None);
+        };
+
+        let bytes = match std::fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                let message = format!("Could not read {}: {}", path.display(), e);
+                return (
+                    State::Failed(Error::new(ErrorKind::Unreadable, message)),
+                    None,
+                );
+            }
+        };
+
+        // BMS empties the file before writing it, there's more to come.
+        if bytes.iter().all(u8::is_ascii_whitespace) {
+            return (State::WaitingForBriefing, None);
+        }
+
+        let modified = path.metadata().and_then(|m| m.modified()).ok();
+        let source = Some(Source::new(&bytes, modified));
+
+        let (parsed, decoded) =
+            Briefing::parse_bytes_with(&bytes, None, options.dictionary.as_ref());
+
+        // Lenient parsing always gives a briefing, so a file without a single
+        // section is reported instead of showing empty tables.
+        if parsed.sections.is_empty() {
+            let error = match decoded.had_errors {
+                true => Error::new(
+                    ErrorKind::Decode,
+                    format!(
+                        "Could not decode {} as {:?}",
+                        path.display(),
+                        decoded.encoding
+                    ),
+                ),
+                false => Error::new(
+                    ErrorKind::Parse,
+                    format!("{} has none of the sections of a briefing", path.display()),
+                ),
+            };
+            return (
+                State::Failed(error.with_details(&parsed.diagnostics)),
+                source,
+            );
+        }
+
+        (State::Ready(Box::new(parsed)), source)
+    }
+
+    pub fn message(&self) -> &'static str {
+        match self {
+            State::WaitingForBms => "Waiting for Falcon BMS to launch...",
+            State::WaitingForBriefing => "Waiting for briefing to be printed...",
+            State::Ready(_) | State::Failed(_) => "",
+        }
+    }
+}
+
+/// The state as of the last reload, along with the pages rendered from it.
+pub struct Snapshot {
+    /// Goes up by one on every reload.
+    pub version: u64,
+    pub state: State,
+    pub source: Option<Source>,
+    pages: Mutex<HashMap<String, String>>,
+}
+
+impl Snapshot {
+    /// Identifies one representation served from this snapshot, such as a
+    /// page or an API response, only set once there is a briefing.
+    ///
+    /// `representation` tells the routes apart, so a page and the JSON of the
+    /// same briefing never share a tag.
+    pub fn etag(&self, representation: &str) -> Option<String> {
+        match (&self.state, self.source) {
+            (State::Ready(_), Some(source)) => {
+                Some(etag(fnv1a(source.hash, representation.as_bytes())))
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether `state`, read from `source`, serves the same as this snapshot.
+    fn same_as(&self, state: &State, source: Option<Source>) -> bool {
+        discriminant(&self.state) == discriminant(state)
+            && self.source.map(|s| s.hash) == source.map(|s| s.hash)
+    }
+
+    /// The page for a layout of sections, rendered the first time it's asked for.
+    ///
+    /// Rendering happens outside the lock, so other layouts are served
+    /// meanwhile. Two first requests for the same layout may both render it,
+    /// the first one to finish is kept.
+    pub fn page<E>(
+        &self,
+        layout: &str,
+        render: impl FnOnce(&State) -> Result<String, E>,
+    ) -> Result<String, E> {
+        if let Some(page) = self.pages.lock().unwrap().get(layout) {
+            return Ok(page.clone());
+        }
+
+        let page = render(&self.state)?;
+        let mut pages = self.pages.lock().unwrap();
+        Ok(pages.entry(layout.to_string()).or_insert(page).clone())
+    }
+}
+
+/// The briefing parsed once per change, shared by every request.
+pub struct Cache {
+    snapshot: RwLock<Arc<Snapshot>>,
+}
+
+impl Default for Cache {
+    fn default() -> Self {
+        Self {
+            snapshot: RwLock::new(Arc::new(Snapshot {
+                version: 0,
+                state: State::WaitingForBms,
+                source: None,
+                pages: Default::default(),
+            })),
+        }
+    }
+}
+
+impl Cache {
+    pub fn get(&self) -> Arc<Snapshot> {
+        self.snapshot.read().unwrap().clone()
+    }
+
+    /// Parses the briefing again, called whenever the watcher sees it change.
+    ///
+    /// Returns whether anything changed, rewriting the same briefing keeps the
+    /// current snapshot and the pages rendered from it.
+    pub fn reload(&self, options: &Options) -> bool {
+        let (state, source) = State::load(options);
+
+        let mut snapshot = self.snapshot.write().unwrap();
+        if snapshot.same_as(&state, source) {
+            return false;
+        }
+
+        // The file was emptied to be written again, keep the briefing until it is.
+        let rewriting = matches!(state, State::WaitingForBriefing) && source.is_none();
+        if rewriting && matches!(snapshot.state, State::Ready(_)) {
+            return false;
+        }
+
+        *snapshot = Arc::new(Snapshot {
+            version: snapshot.version + 1,
+            state,
+            source,
+            pages: Default::default(),
+        });
+
+        true
+    }
+}
@@ -0,0 +1,58 @@
+use std::time::SystemTime;
+
+use axum::{
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+
+/// A strong entity tag for a content hash.
+pub fn etag(hash: u64) -> String {
+    format!("\"{:016x}\"", hash)
+}
+
+/// Answers 304 when the client's `If-None-Match` already has `etag`, and
+/// `response()` tagged with it otherwise.
+///
+/// Clients are asked to check back every time, so a rewritten briefing shows
+/// up on the next reload while an unchanged one costs a 304.
+pub fn conditional(
+    headers: &HeaderMap,
+    etag: Option<String>,
+    modified: Option<SystemTime>,
+    response: impl FnOnce() -> Response,
+) -> Response {
+    let Some(etag) = etag else {
+        return response();
+    };
+
+    let mut response = match matches(headers, &etag) {
+        true => StatusCode::NOT_MODIFIED.into_response(),
+        false => response(),
+    };
+
+    let status = response.status();
+    if status.is_success() || status == StatusCode::NOT_MODIFIED {
+        let headers = response.headers_mut();
+        if let Ok(etag) = HeaderValue::from_str(&etag) {
+            headers.insert(header::ETAG, etag);
+        }
+        if let Some(modified) = modified {
+            if let Ok(date) = HeaderValue::from_str(&httpdate::fmt_http_date(modified)) {
+                headers.insert(header::LAST_MODIFIED, date);
+            }
+        }
+        headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+    }
+
+    response
+}
+
+fn matches(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
@@ -0,0 +1,82 @@
+use std::fmt::Display;
+
+use axum::{
+    http::StatusCode,
+    response::{Html, IntoResponse, Response},
+};
+use serde::Serialize;
+use tera::Context;
+
+use crate::html;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    NotFound,
+    UnknownSection,
+    /// Rendering the page failed.
+    Template,
+    /// The briefing file is there but couldn't be read.
+    Unreadable,
+    /// The briefing isn't text in any encoding BMS writes.
+    Decode,
+    /// Nothing in the briefing file looks like a briefing.
+    Parse,
+}
+
+/// Why a page couldn't be served, shown as an error page or, for API
+/// clients, as JSON.
+#[derive(Debug, Clone, Serialize)]
+pub struct Error {
+    #[serde(rename = "error")]
+    pub kind: ErrorKind,
+    pub message: String,
+    /// What to look at next, the parser's diagnostics or the valid sections.
+    pub details: Vec<String>,
+}
+
+impl Error {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            details: vec![],
+        }
+    }
+
+    pub fn with_details<T: Display>(mut self, details: impl IntoIterator<Item = T>) -> Self {
+        self.details = details.into_iter().map(|d| d.to_string()).collect();
+        self
+    }
+
+    pub fn template(error: impl Display) -> Self {
+        Self::new(
+            ErrorKind::Template,
+            format!("Could not render the page: {}", error),
+        )
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self.kind {
+            ErrorKind::NotFound | ErrorKind::UnknownSection => StatusCode::NOT_FOUND,
+            ErrorKind::Template | ErrorKind::Unreadable | ErrorKind::Decode | ErrorKind::Parse => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        }
+    }
+
+    /// The error page, or just the message when even that doesn't render.
+    pub fn page(&self) -> Response {
+        let mut context = Context::new();
+        context.insert("status", &self.status().as_u16());
+        context.insert("error", self);
+
+        match html::render_error(context) {
+            Ok(page) => (self.status(), Html(page)).into_response(),
+            Err(e) => {
+                println!("{:?}", e);
+                (self.status(), self.message.clone()).into_response()
+            }
+        }
+    }
+}
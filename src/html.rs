@@ -30,6 +30,7 @@ static TERA: Lazy<Tera> = Lazy::new(|| {
     add_template!(tera, "support");
     add_template!(tera, "roe");
     add_template!(tera, "emergency");
+    add_template!(tera, "error");
 
     tera.register_filter("nl2br", nl2br);
 
@@ -47,3 +48,7 @@ fn nl2br(value: &Value, _args: &HashMap<String, Value>) -> TeraResult<Value> {
 pub fn render(context: Context) -> Result<String, Box<dyn std::error::Error>> {
     Ok(TERA.render("index.html.twig", &context)?)
 }
+
+pub fn render_error(context: Context) -> Result<String, Box<dyn std::error::Error>> {
+    Ok(TERA.render("error.html.twig", &context)?)
+}
@@ -1,6 +1,11 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod api;
+mod cache;
+mod conditional;
+mod error;
 mod html;
+mod sections;
 
 #[cfg(target_os = "windows")]
 mod icon;
@@ -11,6 +16,7 @@ mod web;
 #[cfg(target_os = "windows")]
 use bms_sm::{StringData, StringId};
 
+use bms_briefing_parser::Dictionary;
 use clap::Parser;
 
 #[cfg(target_os = "windows")]
@@ -21,7 +27,7 @@ use tokio::time::sleep;
 
 use std::{
     net::SocketAddr,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{Arc, RwLock},
 };
 use tokio::sync::{broadcast, mpsc};
@@ -32,8 +38,11 @@ struct Args {
     /// Webserver listen address:port
     #[arg(short, long, default_value_t = listen_address())]
     listen: SocketAddr,
-    /// Override directory containing briefing.txt, disabled autodetect
+    /// Override directory containing briefing.txt or briefing.html, disabled autodetect
     briefing_dir: Option<PathBuf>,
+    /// Table of translated section headers for briefings in another language
+    #[arg(short, long)]
+    dictionary: Option<PathBuf>,
 }
 
 fn listen_address() -> SocketAddr {
@@ -43,8 +52,14 @@ fn listen_address() -> SocketAddr {
     }
 }
 
+/// What BMS names its briefing, depending on whether "HTML Briefings" is enabled.
+pub const BRIEFING_FILES: [&str; 2] = ["briefing.txt", "briefing.html"];
+
 pub struct Options {
-    pub briefing: RwLock<Option<PathBuf>>,
+    /// The BMS briefings directory, once it is known.
+    pub briefing_dir: RwLock<Option<PathBuf>>,
+    /// Used instead of detecting the briefing's language when set.
+    pub dictionary: Option<Dictionary<'static>>,
 }
 
 #[tokio::main]
@@ -54,8 +69,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let (tx, rx) = mpsc::channel::<()>(1);
     let (close_tx, close_rx) = broadcast::channel::<()>(1);
 
+    let dictionary = match &args.dictionary {
+        Some(path) => Some(Dictionary::parse(&std::fs::read_to_string(path)?)?.into_owned()),
+        None => None,
+    };
+
     let options = Arc::new(Options {
-        briefing: RwLock::new(None),
+        briefing_dir: RwLock::new(None),
+        dictionary,
     });
 
     let options_1 = options.clone();
@@ -69,10 +90,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             return;
         };
 
-        let mut briefing = args.briefing_dir.as_ref().unwrap_or(&briefing_path).clone();
-        briefing.push("briefing.txt");
+        let briefing_dir = args.briefing_dir.as_ref().unwrap_or(&briefing_path).clone();
 
-        *options_1.briefing.write().unwrap() = Some(briefing);
+        *options_1.briefing_dir.write().unwrap() = Some(briefing_dir);
         let _ = tx_1.send(()).await;
     });
 
@@ -101,6 +121,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// The most recently written of the briefing files in `dir`.
+pub fn latest_briefing(dir: &Path) -> Option<PathBuf> {
+    BRIEFING_FILES
+        .iter()
+        .map(|name| dir.join(name))
+        .filter_map(|path| Some((path.metadata().ok()?.modified().ok()?, path)))
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
 #[cfg(target_os = "windows")]
 async fn get_strings() -> HashMap<StringId, String> {
     loop {
@@ -0,0 +1,154 @@
+use bms_briefing_parser::SectionKind;
+
+use crate::error::{Error, ErrorKind};
+
+/// A section of the kneeboard page, the order they're given in is the order
+/// they're shown in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    Overview,
+    Sitrep,
+    Roster,
+    Elements,
+    ThreatAnalysis,
+    Steerpoints,
+    Comms,
+    Iff,
+    Ordnance,
+    Weather,
+    Support,
+    Roe,
+    Emergency,
+}
+
+impl Section {
+    pub const ALL: [Section; 13] = [
+        Section::Overview,
+        Section::Sitrep,
+        Section::Roster,
+        Section::Elements,
+        Section::ThreatAnalysis,
+        Section::Steerpoints,
+        Section::Comms,
+        Section::Iff,
+        Section::Ordnance,
+        Section::Weather,
+        Section::Support,
+        Section::Roe,
+        Section::Emergency,
+    ];
+
+    /// Package Elements, Steerpoints and Comm Ladder.
+    pub const DEFAULT: [Section; 3] = [Section::Elements, Section::Steerpoints, Section::Comms];
+
+    /// The same name as in the JSON API.
+    pub fn name(self) -> &'static str {
+        match self {
+            Section::Overview => "overview",
+            Section::Sitrep => "sitrep",
+            Section::Roster => "roster",
+            Section::Elements => "elements",
+            Section::ThreatAnalysis => "threat_analysis",
+            Section::Steerpoints => "steerpoints",
+            Section::Comms => "comms",
+            Section::Iff => "iff",
+            Section::Ordnance => "ordnance",
+            Section::Weather => "weather",
+            Section::Support => "support",
+            Section::Roe => "roe",
+            Section::Emergency => "emergency",
+        }
+    }
+
+    /// The two letters used in short URLs such as `/PESPCL`, and by the template.
+    pub fn code(self) -> &'static str {
+        match self {
+            Section::Overview => "MO",
+            Section::Sitrep => "SR",
+            Section::Roster => "PR",
+            Section::Elements => "PE",
+            Section::ThreatAnalysis => "TA",
+            Section::Steerpoints => "SP",
+            Section::Comms => "CL",
+            Section::Iff => "IF",
+            Section::Ordnance => "OR",
+            Section::Weather => "WT",
+            Section::Support => "SU",
+            Section::Roe => "RO",
+            Section::Emergency => "EP",
+        }
+    }
+
+    /// The section of the briefing it shows.
+    pub fn kind(self) -> SectionKind {
+        match self {
+            Section::Overview => SectionKind::Overview,
+            Section::Sitrep => SectionKind::Situation,
+            Section::Roster => SectionKind::PilotRoster,
+            Section::Elements => SectionKind::PackageElements,
+            Section::ThreatAnalysis => SectionKind::ThreatAnalysis,
+            Section::Steerpoints => SectionKind::Steerpoints,
+            Section::Comms => SectionKind::CommLadder,
+            Section::Iff => SectionKind::Iff,
+            Section::Ordnance => SectionKind::Ordnance,
+            Section::Weather => SectionKind::Weather,
+            Section::Support => SectionKind::Support,
+            Section::Roe => SectionKind::RulesOfEngagement,
+            Section::Emergency => SectionKind::EmergencyProcedures,
+        }
+    }
+
+    /// By its name or its code, in any case.
+    pub fn find(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|section| {
+            section.name().eq_ignore_ascii_case(name) || section.code().eq_ignore_ascii_case(name)
+        })
+    }
+
+    /// A comma separated list of names or codes, as in `/view/elements,steerpoints,CL`.
+    pub fn parse_list(list: &str) -> Result<Vec<Self>, Error> {
+        let names = list
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .collect::<Vec<_>>();
+
+        if names.is_empty() {
+            return Err(Self::error(format!("No sections in `{}`", list)));
+        }
+
+        Self::parse_all(names)
+    }
+
+    /// Codes run together, as in `/PESPCL`.
+    pub fn parse_codes(codes: &str) -> Result<Vec<Self>, Error> {
+        let chars = codes.chars().collect::<Vec<_>>();
+        let codes = chars.chunks(2).map(String::from_iter).collect::<Vec<_>>();
+        Self::parse_all(codes.iter().map(String::as_str).collect())
+    }
+
+    fn parse_all(names: Vec<&str>) -> Result<Vec<Self>, Error> {
+        let unknown = names
+            .iter()
+            .filter(|name| Self::find(name).is_none())
+            .map(|name| format!("`{}`", name))
+            .collect::<Vec<_>>();
+
+        if !unknown.is_empty() {
+            return Err(Self::error(format!(
+                "Unknown section {}",
+                unknown.join(", ")
+            )));
+        }
+
+        Ok(names.into_iter().filter_map(Self::find).collect())
+    }
+
+    /// Lists every section along with its code.
+    fn error(message: String) -> Error {
+        let sections = Self::ALL
+            .iter()
+            .map(|section| format!("{} ({})", section.name(), section.code()));
+        Error::new(ErrorKind::UnknownSection, message).with_details(sections)
+    }
+}
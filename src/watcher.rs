@@ -8,7 +8,7 @@ use tokio::{
     time::sleep,
 };
 
-use crate::Options;
+use crate::{Options, BRIEFING_FILES};
 
 fn async_watcher() -> notify::Result<(RecommendedWatcher, Receiver<notify::Result<Event>>)> {
     let (tx, rx) = mpsc::channel(1);
@@ -30,7 +30,7 @@ pub fn start(options: Arc<Options>, tx: mpsc::Sender<()>, mut close_rx: broadcas
         let mut closed = false;
         let mut _briefing = None;
         loop {
-            _briefing = options.briefing.read().unwrap().clone();
+            _briefing = options.briefing_dir.read().unwrap().clone();
             let Some(briefing) = _briefing.as_ref() else {
                 tokio::select! {
                     _ = sleep(Duration::from_millis(300)) => {
@@ -44,14 +44,14 @@ pub fn start(options: Arc<Options>, tx: mpsc::Sender<()>, mut close_rx: broadcas
             };
 
             if watcher.watch(briefing, RecursiveMode::NonRecursive).is_ok() {
-                // file exists
-                println!("File loaded, poking");
+                // directory exists
+                println!("Directory loaded, poking");
                 let _ = tx.send(()).await;
                 break;
             }
             tokio::select! {
                 _ = sleep(Duration::from_millis(300)) => {
-                    // file doesnt exist (yet) try again in a bit
+                    // directory doesnt exist (yet) try again in a bit
                 },
                 _ = close_rx.recv() => {
                     let _ = watcher.unwatch(briefing);
@@ -67,9 +67,23 @@ pub fn start(options: Arc<Options>, tx: mpsc::Sender<()>, mut close_rx: broadcas
 
         loop {
             tokio::select! {
-                _ = rx.recv() => {
-                    println!("File changed, poking");
-                    let _ = tx.send(()).await;
+                event = rx.recv() => {
+                    // Other files in the briefings directory are none of our business.
+                    let Some(event) = event else {
+                        break;
+                    };
+                    let Ok(event) = event else {
+                        continue;
+                    };
+                    let briefing = event.paths.iter().any(|path| {
+                        path.file_name()
+                            .and_then(|name| name.to_str())
+                            .is_some_and(|name| BRIEFING_FILES.iter().any(|f| f.eq_ignore_ascii_case(name)))
+                    });
+                    if briefing {
+                        println!("File changed, poking");
+                        let _ = tx.send(()).await;
+                    }
                 }
                 _ = close_rx.recv() => {
                     if let Some(briefing) = _briefing {
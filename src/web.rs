@@ -1,19 +1,17 @@
-use std::{fs::File, io::Read, net::SocketAddr, sync::Arc};
+use std::{collections::HashMap, net::SocketAddr, path::PathBuf, sync::Arc};
 
 use axum::{
     extract::Path,
-    http::Response,
+    http::{header, HeaderMap, StatusCode},
     response::{
         sse::{Event, KeepAlive},
-        Html, Sse,
+        Html, IntoResponse, Response, Sse,
     },
     routing::get,
     Extension, Router,
 };
-use bms_briefing_parser::*;
+use bms_briefing_parser::Diagnostic;
 use convert_case::{Case, Casing};
-use encoding_rs::WINDOWS_1252;
-use encoding_rs_io::DecodeReaderBytesBuilder;
 use serde::Serialize;
 use serde_type_name::type_name;
 use tera::Context;
@@ -23,7 +21,15 @@ use tokio_stream::{
     Stream,
 };
 
-use crate::{html, Options};
+use crate::{
+    api,
+    cache::{content_hash, Cache, State},
+    conditional::{conditional, etag},
+    error::Error,
+    html,
+    sections::Section,
+    Options,
+};
 
 pub fn start(
     listen: SocketAddr,
@@ -33,13 +39,22 @@ pub fn start(
     mut close_sse_rx: broadcast::Receiver<()>,
 ) {
     let sse_service = SseService::new();
+    let cache = Arc::new(Cache::default());
 
     let sse_poker = sse_service.clone();
+    let reloader = (options.clone(), cache.clone());
     tokio::spawn(async move {
         loop {
             tokio::select! {
                 _ = rx.recv() => {
-                    let _ = sse_poker.push(&Poke {});
+                    // Parse once here, so pages opened by the poke are served from the cache.
+                    // Pages only reload when there is something new to show.
+                    // Reading and parsing the file blocks, keep it off the async workers.
+                    let (options, cache) = reloader.clone();
+                    let reloaded = tokio::task::spawn_blocking(move || cache.reload(&options)).await;
+                    if matches!(reloaded, Ok(true)) {
+                        let _ = sse_poker.push(&Poke {});
+                    }
                 }
                 _ = close_sse_rx.recv() => {
                     break;
@@ -53,9 +68,15 @@ pub fn start(
             .route("/sse", get(sse))
             .route("/favicon.ico", get(a404))
             .route("/style.css", get(style))
+            .route("/api/briefing", get(api::briefing))
+            .route("/api/briefing/:section", get(api::section))
+            .route("/api/*path", get(api::not_found))
             .route("/", get(index))
+            .route("/view", get(index))
+            .route("/view/", get(index))
+            .route("/view/:sections", get(view))
             .route("/*key", get(index_params))
-            .layer(Extension(options.clone()))
+            .layer(Extension(cache.clone()))
             .layer(Extension(sse_service.clone()));
 
         let listener = tokio::net::TcpListener::bind(listen).await.unwrap();
@@ -74,15 +95,27 @@ pub fn start(
 #[derive(Serialize)]
 struct Poke;
 
-async fn style() -> Response<String> {
-    // read file next to executable called style.css
-    let file = std::fs::read_to_string("style.css").unwrap_or_default(); // empty string as default
+async fn style(headers: HeaderMap) -> Response {
+    // style.css next to the executable, or in the working directory when running from source
+    let next_to_exe = std::env::current_exe()
+        .ok()
+        .and_then(|exe| Some(exe.parent()?.join("style.css")));
+
+    for path in next_to_exe.into_iter().chain([PathBuf::from("style.css")]) {
+        if let Ok(css) = std::fs::read_to_string(&path) {
+            let etag = etag(content_hash(css.as_bytes()));
+            let modified = path.metadata().and_then(|m| m.modified()).ok();
+            return conditional(&headers, Some(etag), modified, || {
+                ([(header::CONTENT_TYPE, "text/css")], css).into_response()
+            });
+        }
+    }
 
-    Response::new(file)
+    (StatusCode::NOT_FOUND, "style.css not found").into_response()
 }
 
-async fn a404() -> Response<String> {
-    Response::new("404".to_owned())
+async fn a404() -> StatusCode {
+    StatusCode::NOT_FOUND
 }
 
 async fn sse(
@@ -91,123 +124,101 @@ async fn sse(
     Sse::new(BroadcastStream::new(sse.subscribe())).keep_alive(KeepAlive::default())
 }
 
-async fn index(Extension(options): Extension<Arc<Options>>) -> Html<String> {
-    index_params(axum::Extension(options), Path("PESPCL".to_string())).await
+async fn index(Extension(cache): Extension<Arc<Cache>>, headers: HeaderMap) -> Response {
+    page(&cache, &Section::DEFAULT, &headers)
 }
 
+/// `/view/elements,steerpoints,comms`, the sections by name or code.
+async fn view(
+    Extension(cache): Extension<Arc<Cache>>,
+    Path(sections): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    match Section::parse_list(&sections) {
+        Ok(sections) => page(&cache, &sections, &headers),
+        Err(error) => error.page(),
+    }
+}
+
+/// `/PESPCL`, the sections by their codes.
 async fn index_params(
-    Extension(options): Extension<Arc<Options>>,
+    Extension(cache): Extension<Arc<Cache>>,
     Path(key): Path<String>,
-) -> Html<String> {
-    let Ok(subs) = key
-        .as_bytes()
-        .chunks(2)
-        .map(std::str::from_utf8)
-        .collect::<Result<Vec<&str>, _>>()
-    else {
-        return Html("501".to_string());
-    };
-
-    let briefing = &options.briefing.read().unwrap();
-
-    let mut context = Context::new();
+    headers: HeaderMap,
+) -> Response {
+    match Section::parse_codes(&key) {
+        Ok(sections) => page(&cache, &sections, &headers),
+        Err(error) => error.page(),
+    }
+}
 
-    context.insert("subs", &subs);
+fn page(cache: &Cache, sections: &[Section], headers: &HeaderMap) -> Response {
+    // Pages are cached by their codes, so the short and long URL share one.
+    let subs = sections.iter().map(|s| s.code()).collect::<Vec<_>>();
+    let key = subs.concat();
 
-    let Some(briefing) = briefing.as_ref() else {
-        context.insert("msg", "Waiting for Falcon BMS to launch...");
+    let snapshot = cache.get();
+    let status = match &snapshot.state {
+        State::Ready(_) => StatusCode::OK,
+        State::WaitingForBms | State::WaitingForBriefing => StatusCode::SERVICE_UNAVAILABLE,
+        State::Failed(error) => return error.page(),
+    };
 
-        let render = match html::render(context) {
-            Ok(e) => e,
+    let modified = snapshot.source.and_then(|s| s.modified);
+    let etag = snapshot.etag(&format!("/view/{}", key));
+    conditional(headers, etag, modified, || {
+        match snapshot.page(&key, |state| render(&subs, state)) {
+            Ok(page) => (status, Html(page)).into_response(),
             Err(e) => {
                 println!("{:?}", e);
-                return Html(String::from("501"));
+                Error::template(e).page()
             }
-        };
-
-        return Html(render);
-    };
-
-    let briefing = match File::open(briefing) {
-        Ok(e) => e,
-        Err(e) => {
-            dbg!(e);
-            context.insert("msg", "Waiting for briefing to be printed...");
-
-            let render = match html::render(context) {
-                Ok(e) => e,
-                Err(e) => {
-                    println!("{:?}", e);
-                    return Html(String::from("501"));
-                }
-            };
-
-            return Html(render);
         }
-    };
+    })
+}
 
-    let mut buf = String::new();
-    if let Err(e) = DecodeReaderBytesBuilder::new()
-        .encoding(Some(WINDOWS_1252))
-        .build(briefing)
-        .read_to_string(&mut buf)
-    {
-        println!("{:?}", e);
-        return Html(String::from("501"));
-    }
-    buf = buf
-        .replace("\r\n", "\n")
-        .replace(|c: char| !c.is_ascii(), "");
-
-    let mut commladder = Comm::from_briefing(&buf);
-
-    commladder.iter_mut().for_each(|c| {
-        if let Some(callsign) = c.callsign.as_mut() {
-            if let Some(c) = callsign
-                .split(|c: char| !c.is_alphanumeric() && !c.is_whitespace() && c != '-')
-                .next()
-            {
-                *callsign = c;
-            }
-        };
-    });
+fn render(subs: &[&str], state: &State) -> Result<String, Box<dyn std::error::Error>> {
+    let mut context = Context::new();
 
-    let overview = Overview::from_briefing(&buf);
-    let sitrep = Sitrep::from_briefing(&buf);
-    let roster = PilotRoster::from_briefing(&buf);
-    let elements = PackageElement::from_briefing(&buf);
-    let threatanalysis = ThreatAnalysis::from_briefing(&buf);
-    let steerpoints = Steerpoint::from_briefing(&buf);
-    let iff = Iff::from_briefing(&buf);
-    let ordnance = Ordnance::from_briefing(&buf);
-    let weather = Weather::from_briefing(&buf);
-    let support = Support::from_briefing(&buf);
-    let roe = RulesOfEngagement::from_briefing(&buf);
-    let emergency = Emergency::from_briefing(&buf);
-
-    context.insert("overview", &overview);
-    context.insert("sitrep", &sitrep);
-    context.insert("roster", &roster);
-    context.insert("elements", &elements);
-    context.insert("threatanalysis", &threatanalysis);
-    context.insert("steerpoints", &steerpoints);
-    context.insert("commladder", &commladder);
-    context.insert("iff", &iff);
-    context.insert("ordnance", &ordnance);
-    context.insert("weather", &weather);
-    context.insert("support", &support);
-    context.insert("roe", &roe);
-    context.insert("emergency", &emergency);
-
-    let render = match html::render(context) {
-        Ok(e) => e,
-        Err(e) => {
-            println!("{:?}", e);
-            return Html(String::from("501"));
-        }
-    };
+    context.insert("subs", subs);
 
-    Html(render)
+    // Failed states have their own page, they don't get here.
+    let State::Ready(parsed) = state else {
+        context.insert("msg", state.message());
+        return html::render(context);
+    };
+    let briefing = &parsed.briefing;
+
+    // What the parser had to say about a section is shown above it.
+    let diagnostics: HashMap<&str, Vec<&Diagnostic>> = subs
+        .iter()
+        .map(|&code| {
+            let kind = Section::find(code).map(Section::kind);
+            let diagnostics = parsed
+                .diagnostics
+                .iter()
+                .filter(|d| Some(d.section) == kind)
+                .collect();
+            (code, diagnostics)
+        })
+        .collect();
+    context.insert("diagnostics", &diagnostics);
+
+    context.insert("overview", &briefing.overview);
+    context.insert("sitrep", &briefing.sitrep);
+    context.insert("roster", &briefing.roster);
+    context.insert("elements", &briefing.elements);
+    context.insert("threatanalysis", &briefing.threat_analysis);
+    context.insert("steerpoints", &briefing.steerpoints);
+    context.insert("commladder", &briefing.comms_with_support());
+    context.insert("iff", &briefing.iff);
+    context.insert("ordnance", &briefing.ordnance);
+    context.insert("weather", &briefing.weather);
+    context.insert("support", &briefing.support);
+    context.insert("roe", &briefing.roe);
+    context.insert("emergency", &briefing.emergency);
+
+    html::render(context)
 }
 
 #[derive(Debug, Clone)]